@@ -0,0 +1,40 @@
+use std::fmt::{self, Display};
+
+#[cfg(feature = "http")]
+use http::HeaderValue;
+
+use crate::headers::UNIT;
+
+/// A typed HTTP `Accept-Ranges` header.
+///
+/// Per [RFC 9110 Section 14.3], a server sends `Accept-Ranges: none` to tell
+/// clients not to attempt a range request for the target resource.
+///
+/// [RFC 9110 Section 14.3]: https://www.rfc-editor.org/rfc/rfc9110#section-14.3
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AcceptRanges {
+    /// Byte ranges are supported.
+    #[default]
+    Bytes,
+    /// Ranges are not supported.
+    None,
+}
+
+#[cfg(feature = "http")]
+impl From<&AcceptRanges> for HeaderValue {
+    fn from(value: &AcceptRanges) -> Self {
+        match value {
+            AcceptRanges::Bytes => HeaderValue::from_static(UNIT),
+            AcceptRanges::None => HeaderValue::from_static("none"),
+        }
+    }
+}
+
+impl Display for AcceptRanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AcceptRanges::Bytes => f.write_str(UNIT),
+            AcceptRanges::None => f.write_str("none"),
+        }
+    }
+}
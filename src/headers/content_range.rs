@@ -1,18 +1,34 @@
 use std::{
     fmt::{self, Display},
-    ops::RangeInclusive,
+    num::NonZeroU64,
+    ops::{Range, RangeInclusive},
     str::FromStr,
 };
 
-use http::HeaderValue;
-
-use crate::headers::{
-    InvalidHttpU64, InvalidOrderedRange, OrderedRange, ParseHttpRangeOrContentRangeError, UNIT,
-    range::HttpRange, u64_unprefixed_parse,
+#[cfg(feature = "http")]
+use http::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::{
+    UnsatisfiableRange,
+    headers::{
+        HeaderKind, InvalidHttpU64, InvalidOrderedRange, OrderedRange,
+        ParseHttpRangeOrContentRangeError, ParseMode, UNIT,
+        range::HttpRange,
+        range_unit::{RangeUnit, WithUnit},
+        u64_unprefixed_parse,
+    },
 };
 
+const HEADER_KIND: HeaderKind = HeaderKind::ContentRange;
+
 /// A typed HTTP `Content-Range` header that only supports a __single__ range.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// This enum is `#[non_exhaustive]`: a `match` on it needs a wildcard arm.
+/// Code that does not need the variants can use the constructors, such as
+/// [`HttpContentRange::bound`], and the accessors, such as [`HttpContentRange::as_bound`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum HttpContentRange {
     Bound(Bound),
     Unsatisfiable(Unsatisfiable),
@@ -21,6 +37,11 @@ pub enum HttpContentRange {
 impl HttpContentRange {
     /// Checks whether this `Content-Range` matches the expected [`HttpRange`].
     ///
+    /// A [`HttpContentRange::Unsatisfiable`] only matches a range that genuinely
+    /// cannot be satisfied at the size it carries: an int-range or a starting
+    /// point whose first byte is at or past the size, or a zero-length suffix.
+    /// A `416` for a range starting within the representation does not match.
+    ///
     /// [`HttpRange`]: crate::headers::range::HttpRange
     pub fn matches_requested_range(&self, expected_range: HttpRange) -> bool {
         match (expected_range, self) {
@@ -32,8 +53,12 @@ impl HttpContentRange {
                 HttpContentRange::Bound(Bound { range, .. }),
             ) => start == range.start() && end >= range.end(),
             (HttpRange::Suffix(suffix), HttpContentRange::Bound(Bound { range, size })) => {
+                // A suffix longer than the representation is clamped by the
+                // server to the whole representation (RFC 9110 Section 14.1.2),
+                // so a shorter length is only acceptable when starting at 0.
                 let length = (range.end() - range.start()).checked_add(1);
-                let length_matches = length.is_some_and(|len| len <= suffix);
+                let length_matches =
+                    length.is_some_and(|len| len == suffix || (len < suffix && range.start() == 0));
                 let ends_at_boundary = size.is_none_or(|size| range.end() + 1 == size);
                 length_matches && ends_at_boundary
             }
@@ -49,73 +74,333 @@ impl HttpContentRange {
                 HttpRange::Range(OrderedRange { start: n, .. }),
                 HttpContentRange::Unsatisfiable(Unsatisfiable { size }),
             ) => n >= *size,
+            // A non-zero suffix-range is satisfiable whatever the size, even
+            // for an empty representation.
             (HttpRange::Suffix(suffix), HttpContentRange::Unsatisfiable(Unsatisfiable { .. })) => {
                 suffix == 0
             }
         }
     }
-}
 
-impl FromStr for HttpContentRange {
-    type Err = ParseHttpRangeOrContentRangeError;
+    /// Like [`HttpContentRange::matches_requested_range`], but also checks the
+    /// response against the `size` of the representation.
+    ///
+    /// The range must be exactly the one [`file_range`] would serve for
+    /// `expected_range`, so that a truncated response such as `bytes 50-60/200`
+    /// for a `bytes=50-` request is detected, and a known size must be equal to `size`.
+    ///
+    /// [`file_range`]: crate::file_range
+    pub fn matches_requested_range_strict(&self, expected_range: HttpRange, size: u64) -> bool {
+        match self {
+            HttpContentRange::Bound(bound) => {
+                bound.size.is_none_or(|bound_size| bound_size == size)
+                    && expected_range.resolve(size) == Some(bound.range)
+            }
+            HttpContentRange::Unsatisfiable(unsatisfiable) => {
+                unsatisfiable.size == size && expected_range.resolve(size).is_none()
+            }
+        }
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Creates a [`HttpContentRange::Bound`], returning [`InvalidBound`] under the
+    /// same conditions as [`Bound::new`].
+    ///
+    /// ```
+    /// use range_requests::headers::content_range::HttpContentRange;
+    ///
+    /// let content_range = HttpContentRange::bound(0..=99, Some(200)).unwrap();
+    /// assert_eq!(content_range.to_string(), "bytes 0-99/200");
+    /// ```
+    pub fn bound(range: RangeInclusive<u64>, size: Option<u64>) -> Result<Self, InvalidBound> {
+        Bound::new(range, size).map(HttpContentRange::Bound)
+    }
+
+    /// Creates a [`HttpContentRange::Unsatisfiable`] for a representation of `size` bytes.
+    pub fn unsatisfiable(size: u64) -> Self {
+        HttpContentRange::Unsatisfiable(Unsatisfiable { size })
+    }
+
+    /// Creates the `bytes */0` [`HttpContentRange::Unsatisfiable`] of an empty representation.
+    ///
+    /// Every int-range is unsatisfiable for an empty representation, so this is
+    /// the `Content-Range` of a `416` response to a range request for an empty file.
+    pub fn empty_resource() -> Self {
+        Self::unsatisfiable(0)
+    }
+
+    /// Returns the [`Bound`] if this is a [`HttpContentRange::Bound`].
+    pub fn as_bound(&self) -> Option<Bound> {
+        match self {
+            HttpContentRange::Bound(bound) => Some(*bound),
+            HttpContentRange::Unsatisfiable(_) => None,
+        }
+    }
+
+    /// Returns the [`Unsatisfiable`] if this is a [`HttpContentRange::Unsatisfiable`].
+    pub fn as_unsatisfiable(&self) -> Option<Unsatisfiable> {
+        match self {
+            HttpContentRange::Bound(_) => None,
+            HttpContentRange::Unsatisfiable(unsatisfiable) => Some(*unsatisfiable),
+        }
+    }
+
+    /// Returns the inclusive range of a [`HttpContentRange::Bound`] as `usize`, to
+    /// slice the body the range was received for.
+    ///
+    /// Returns `None` for a [`HttpContentRange::Unsatisfiable`], or if the range
+    /// does not fit a `usize`, as may happen on 32-bit targets.
+    pub fn resolved_slice(&self) -> Option<RangeInclusive<usize>> {
+        let range = self.as_bound()?.range();
+        let start = usize::try_from(range.start()).ok()?;
+        let end = usize::try_from(range.end()).ok()?;
+
+        Some(start..=end)
+    }
+
+    /// Returns `true` if this is a [`HttpContentRange::Bound`].
+    ///
+    /// ```
+    /// use range_requests::headers::content_range::HttpContentRange;
+    ///
+    /// let partial = "bytes 0-99/200".parse::<HttpContentRange>().unwrap();
+    /// assert!(partial.is_satisfiable());
+    ///
+    /// let unsatisfiable = "bytes */200".parse::<HttpContentRange>().unwrap();
+    /// assert!(unsatisfiable.is_unsatisfiable());
+    /// ```
+    pub fn is_satisfiable(&self) -> bool {
+        matches!(self, HttpContentRange::Bound(_))
+    }
+
+    /// Returns `true` if this is a [`HttpContentRange::Unsatisfiable`].
+    pub fn is_unsatisfiable(&self) -> bool {
+        matches!(self, HttpContentRange::Unsatisfiable(_))
+    }
+
+    /// Returns the offset at which the received part belongs in the representation,
+    /// that is the start of the [`Bound`] range.
+    ///
+    /// It is `None` for an [`Unsatisfiable`], which carries no part.
+    pub fn write_offset(&self) -> Option<u64> {
+        match self {
+            HttpContentRange::Bound(bound) => Some(bound.range.start()),
+            HttpContentRange::Unsatisfiable(_) => None,
+        }
+    }
+
+    /// Creates the `Content-Range` of a response to a `bytes=-suffix` request on a
+    /// representation of `size` bytes.
+    ///
+    /// A suffix longer than the representation is clamped to the whole
+    /// representation, while a suffix of `0` is unsatisfiable.
+    pub fn from_suffix(suffix: u64, size: NonZeroU64) -> Result<Self, UnsatisfiableRange> {
+        match HttpRange::Suffix(suffix).resolve(size.get()) {
+            Some(range) => Ok(HttpContentRange::Bound(Bound {
+                range,
+                size: Some(size.get()),
+            })),
+            None => Err(UnsatisfiableRange(HttpContentRange::Unsatisfiable(
+                Unsatisfiable::new(size.get()),
+            ))),
+        }
+    }
+
+    /// Extracts and parses the `Content-Range` header from a [`HeaderMap`].
+    ///
+    /// Returns `Ok(None)` if the header is absent, and an error if it is
+    /// present more than once.
+    #[cfg(feature = "http")]
+    pub fn from_header_map(
+        map: &HeaderMap,
+    ) -> Result<Option<Self>, ParseHttpRangeOrContentRangeError> {
+        crate::headers::single_header_value(map, http::header::CONTENT_RANGE, HEADER_KIND)?
+            .map(Self::try_from)
+            .transpose()
+    }
+
+    /// Returns the `Content-Range` header name and value, ready to be inserted into a [`HeaderMap`].
+    #[cfg(feature = "http")]
+    pub fn as_header_pair(&self) -> (HeaderName, HeaderValue) {
+        (http::header::CONTENT_RANGE, HeaderValue::from(self))
+    }
+
+    /// Checks whether this `Content-Range` covers the whole representation of
+    /// `total_known_size` bytes.
+    ///
+    /// A [`Bound`] with an unknown size (`*`) is considered complete if its range
+    /// spans `0..=total_known_size - 1`. An [`Unsatisfiable`] response is never complete.
+    pub fn is_complete_response(&self, total_known_size: u64) -> bool {
+        match self {
+            HttpContentRange::Bound(bound) => {
+                let range = bound.range();
+                let size_matches = bound.size().is_none_or(|size| size == total_known_size);
+                size_matches
+                    && range.start() == 0
+                    && range.end().checked_add(1) == Some(total_known_size)
+            }
+            HttpContentRange::Unsatisfiable(_) => false,
+        }
+    }
+
+    /// Checks whether this `Content-Range` carries the whole representation, as
+    /// expected in response to a `bytes=0-` request.
+    ///
+    /// Unlike [`HttpContentRange::is_complete_response`], the size is taken from the
+    /// header itself, so a [`Bound`] with an unknown size (`*`) is never full.
+    pub fn is_full_from_start(&self) -> bool {
+        match self {
+            HttpContentRange::Bound(bound) => bound
+                .size()
+                .is_some_and(|size| self.is_complete_response(size)),
+            HttpContentRange::Unsatisfiable(_) => false,
+        }
+    }
+
+    /// Parses a `Content-Range` header value, treating an empty size as unknown.
+    ///
+    /// Some servers send `bytes 0-99/` to mean `bytes 0-99/*`. [`FromStr`]
+    /// rejects it with [`InvalidSize`], while this method returns a [`Bound`]
+    /// without a size.
+    ///
+    /// [`InvalidSize`]: ParseHttpRangeOrContentRangeError::InvalidSize
+    pub fn parse_lenient(s: &str) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        Self::parse(s, ParseMode::Lenient)
+    }
+
+    /// Parses a `Content-Range` header value like [`FromStr`], but accepts any
+    /// range unit and keeps it with the range, instead of rejecting units other
+    /// than `bytes` with [`InvalidUnit`].
+    ///
+    /// The range is parsed with the syntax of the `bytes` unit, whatever the unit.
+    ///
+    /// [`InvalidUnit`]: ParseHttpRangeOrContentRangeError::InvalidUnit
+    pub fn parse_with_unit(s: &str) -> Result<WithUnit<Self>, ParseHttpRangeOrContentRangeError> {
+        let (unit, content_range) = Self::parse_unit(s, ParseMode::Standard, true)?;
+
+        Ok(WithUnit::new(unit, content_range))
+    }
+
+    fn parse(s: &str, mode: ParseMode) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        Self::parse_unit(s, mode, false).map(|(_, content_range)| content_range)
+    }
+
+    /// Parses a `Content-Range` header value, rejecting units other than `bytes`
+    /// unless `any_unit` is set.
+    fn parse_unit(
+        s: &str,
+        mode: ParseMode,
+        any_unit: bool,
+    ) -> Result<(RangeUnit, Self), ParseHttpRangeOrContentRangeError> {
         let s = s.trim();
         if s.is_empty() {
-            return Err(ParseHttpRangeOrContentRangeError::Empty);
+            return Err(ParseHttpRangeOrContentRangeError::Empty(HEADER_KIND));
         }
 
         let (unit_str, range_and_size_str) = s
             .split_once(" ")
-            .ok_or(ParseHttpRangeOrContentRangeError::Malformed)?;
+            .ok_or(ParseHttpRangeOrContentRangeError::Malformed(HEADER_KIND))?;
 
-        // Range unit names are case-insensitive (RFC 9110 Section 14.1).
-        if !unit_str.eq_ignore_ascii_case(UNIT) {
-            return Err(ParseHttpRangeOrContentRangeError::InvalidUnit);
-        }
+        let unit = if any_unit {
+            RangeUnit::from_token(unit_str, HEADER_KIND)?
+        } else if unit_str.eq_ignore_ascii_case(UNIT) {
+            // Range unit names are case-insensitive (RFC 9110 Section 14.1).
+            RangeUnit::Bytes
+        } else {
+            return Err(ParseHttpRangeOrContentRangeError::InvalidUnit(HEADER_KIND));
+        };
 
-        let (range_str, size_str) = range_and_size_str
-            .split_once('/')
-            .ok_or(ParseHttpRangeOrContentRangeError::Malformed)?;
+        let (range_str, size_str) = range_and_size_str.split_once('/').ok_or(
+            ParseHttpRangeOrContentRangeError::MissingSizeDelimiter(HEADER_KIND),
+        )?;
 
         let range = range_str.parse::<ParsedRange>()?;
-        let size = size_str
-            .parse::<ParsedSize>()
-            .map_err(ParseHttpRangeOrContentRangeError::InvalidSize)?;
-
-        match (range, size) {
+        let size = match size_str {
+            "" if mode == ParseMode::Lenient => ParsedSize::Star,
+            size_str => size_str
+                .parse::<ParsedSize>()
+                .map_err(|err| ParseHttpRangeOrContentRangeError::InvalidSize(HEADER_KIND, err))?,
+        };
+
+        let content_range = match (range, size) {
             (ParsedRange::Star, ParsedSize::Star) => {
-                Err(ParseHttpRangeOrContentRangeError::Malformed)
+                return Err(ParseHttpRangeOrContentRangeError::Malformed(HEADER_KIND));
             }
             (ParsedRange::Star, ParsedSize::Value(size)) => {
-                Ok(Self::Unsatisfiable(Unsatisfiable { size }))
+                Self::Unsatisfiable(Unsatisfiable { size })
             }
             (ParsedRange::Range(range), ParsedSize::Star) => {
-                Ok(Self::Bound(Bound { range, size: None }))
-            }
-            (ParsedRange::Range(range), ParsedSize::Value(size)) if range.end() < size => {
-                Ok(Self::Bound(Bound {
-                    range,
-                    size: Some(size),
-                }))
-            }
-            (ParsedRange::Range(_), ParsedSize::Value(_)) => {
-                Err(ParseHttpRangeOrContentRangeError::MalformedRange)
+                Self::Bound(Bound::new(range.start()..=range.end(), None).map_err(bound_error)?)
             }
+            (ParsedRange::Range(range), ParsedSize::Value(size)) => Self::Bound(
+                Bound::new(range.start()..=range.end(), Some(size)).map_err(bound_error)?,
+            ),
+        };
+
+        Ok((unit, content_range))
+    }
+}
+
+/// Maps the error of [`Bound::new`] for a parsed `Content-Range` bound, so that
+/// the parser and [`Bound::new`] reject the same values.
+pub(crate) fn bound_error(err: InvalidBound) -> ParseHttpRangeOrContentRangeError {
+    match err {
+        InvalidBound::InvalidRange(err) => {
+            ParseHttpRangeOrContentRangeError::UnorderedRange(HEADER_KIND, err)
         }
+        InvalidBound::StartBeyondSize { .. } => {
+            ParseHttpRangeOrContentRangeError::StartBeyondSize(HEADER_KIND)
+        }
+        InvalidBound::InvalidSize { .. } | InvalidBound::EmptyRange => {
+            ParseHttpRangeOrContentRangeError::MalformedRange(HEADER_KIND)
+        }
+    }
+}
+
+impl FromStr for HttpContentRange {
+    type Err = ParseHttpRangeOrContentRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, ParseMode::Standard)
     }
 }
 
 /// The Errors that may occur when creating a [`Bound`].
+///
+/// This enum is `#[non_exhaustive]`, since new kinds of errors may be detected:
+/// a `match` on it needs a wildcard arm.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
 pub enum InvalidBound {
     #[error(transparent)]
     InvalidRange(#[from] InvalidOrderedRange),
     #[error("The provided range `end`: {} is greater than or equal to `size`: {size}", range.end)]
     InvalidSize { range: OrderedRange, size: u64 },
+    #[error("The provided range `start`: {} is greater than or equal to `size`: {size}", range.start)]
+    StartBeyondSize { range: OrderedRange, size: u64 },
+    #[error("The provided range is empty")]
+    EmptyRange,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl InvalidBound {
+    /// Returns by how many bytes the range exceeds the size, for
+    /// [`InvalidBound::InvalidSize`] and [`InvalidBound::StartBeyondSize`].
+    ///
+    /// This is `range.end() + 1 - size`, saturating at `u64::MAX`. It is `None`
+    /// if the range does not actually exceed the size, as may happen for a value
+    /// built by hand.
+    pub fn overflow(&self) -> Option<u64> {
+        match self {
+            InvalidBound::InvalidSize { range, size }
+            | InvalidBound::StartBeyondSize { range, size } => range
+                .end()
+                .checked_sub(*size)
+                .map(|overflow| overflow.saturating_add(1)),
+            InvalidBound::InvalidRange(_) | InvalidBound::EmptyRange => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Bound {
     range: OrderedRange,
     size: Option<u64>,
@@ -126,28 +411,79 @@ impl Bound {
     pub fn new(range: RangeInclusive<u64>, size: Option<u64>) -> Result<Self, InvalidBound> {
         let range = OrderedRange::new(range)?;
 
-        if let Some(size) = size
-            && range.end() >= size
-        {
-            return Err(InvalidBound::InvalidSize { range, size });
+        if let Some(size) = size {
+            // A range starting past the end is a clearer sign of a broken server
+            // than one only ending past it, so it is reported on its own.
+            if range.start() >= size {
+                return Err(InvalidBound::StartBeyondSize { range, size });
+            }
+            if range.end() >= size {
+                return Err(InvalidBound::InvalidSize { range, size });
+            }
         }
 
         Ok(Self { range, size })
     }
 
+    /// Creates a new [`Bound`] from a half-open range, such as the one used to
+    /// slice a body.
+    ///
+    /// `start..end` is converted to `start..=end - 1`, so an empty range returns
+    /// [`InvalidBound::EmptyRange`].
+    pub fn from_exclusive(range: Range<u64>, size: Option<u64>) -> Result<Self, InvalidBound> {
+        if range.is_empty() {
+            return Err(InvalidBound::EmptyRange);
+        }
+
+        Self::new(range.start..=range.end - 1, size)
+    }
+
     // Returns a copy of the [`Bound`] range.
     pub fn range(&self) -> OrderedRange {
         self.range
     }
 
+    /// Returns the range of the [`Bound`] as a [`RangeInclusive`].
+    pub fn range_inclusive(&self) -> RangeInclusive<u64> {
+        self.range.start..=self.range.end
+    }
+
     // Returns the size of the [`Bound`], if present.
     pub fn size(&self) -> Option<u64> {
         self.size
     }
+
+    /// Returns `true` if the size of the representation is known, i.e. it is not `*`.
+    pub fn is_size_known(&self) -> bool {
+        self.size.is_some()
+    }
+
+    /// Returns `true` if both [`Bound`]s cover the same range, regardless of their size.
+    ///
+    /// For instance `bytes 0-99/*` and `bytes 0-99/200` have the same range.
+    pub fn same_range(&self, other: &Bound) -> bool {
+        self.range == other.range
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Bound {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let range = OrderedRange::arbitrary(u)?;
+        // The size must be strictly greater than the range end, which is only
+        // representable if the end is not `u64::MAX`.
+        let size = match range.end().checked_add(1) {
+            Some(min_size) if bool::arbitrary(u)? => Some(u.int_in_range(min_size..=u64::MAX)?),
+            _ => None,
+        };
+
+        Ok(Self { range, size })
+    }
 }
 
 // An unsatisfiable `Content-Range`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Unsatisfiable {
     size: u64,
 }
@@ -173,16 +509,21 @@ impl FromStr for ParsedRange {
             return Ok(ParsedRange::Star);
         }
 
-        let (start_str, end_str) = s
-            .split_once('-')
-            .ok_or(ParseHttpRangeOrContentRangeError::MalformedRange)?;
-
-        let start = u64_unprefixed_parse(start_str)
-            .map_err(ParseHttpRangeOrContentRangeError::InvalidRangePiece)?;
-        let end = u64_unprefixed_parse(end_str)
-            .map_err(ParseHttpRangeOrContentRangeError::InvalidRangePiece)?;
-
-        let range = OrderedRange::new(start..=end)?;
+        let (start_str, end_str) =
+            s.split_once('-')
+                .ok_or(ParseHttpRangeOrContentRangeError::MalformedRange(
+                    HEADER_KIND,
+                ))?;
+
+        let start = u64_unprefixed_parse(start_str).map_err(|err| {
+            ParseHttpRangeOrContentRangeError::InvalidRangePiece(HEADER_KIND, err)
+        })?;
+        let end = u64_unprefixed_parse(end_str).map_err(|err| {
+            ParseHttpRangeOrContentRangeError::InvalidRangePiece(HEADER_KIND, err)
+        })?;
+
+        let range = OrderedRange::new(start..=end)
+            .map_err(|err| ParseHttpRangeOrContentRangeError::UnorderedRange(HEADER_KIND, err))?;
         Ok(ParsedRange::Range(range))
     }
 }
@@ -206,6 +547,7 @@ impl FromStr for ParsedSize {
     }
 }
 
+#[cfg(feature = "http")]
 impl From<&HttpContentRange> for HeaderValue {
     fn from(value: &HttpContentRange) -> Self {
         HeaderValue::from_maybe_shared(value.to_string())
@@ -213,16 +555,26 @@ impl From<&HttpContentRange> for HeaderValue {
     }
 }
 
+#[cfg(feature = "http")]
 impl TryFrom<&HeaderValue> for HttpContentRange {
     type Error = ParseHttpRangeOrContentRangeError;
     fn try_from(value: &HeaderValue) -> Result<Self, Self::Error> {
         value
             .to_str()
-            .map_err(|_| ParseHttpRangeOrContentRangeError::ContainsNonVisibleASCII)?
+            .map_err(|_| ParseHttpRangeOrContentRangeError::ContainsNonVisibleASCII(HEADER_KIND))?
             .parse::<Self>()
     }
 }
 
+#[cfg(feature = "http")]
+impl TryFrom<HeaderValue> for HttpContentRange {
+    type Error = ParseHttpRangeOrContentRangeError;
+
+    fn try_from(value: HeaderValue) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
 #[cfg(feature = "axum")]
 impl<S> axum_core::extract::OptionalFromRequestParts<S> for HttpContentRange
 where
@@ -234,24 +586,25 @@ where
         parts: &mut http::request::Parts,
         _state: &S,
     ) -> Result<Option<Self>, Self::Rejection> {
-        match parts.headers.get(http::header::CONTENT_RANGE) {
-            Some(content_range) => {
-                let content_range = HttpContentRange::try_from(content_range)?;
-                Ok(Some(content_range))
-            }
-            None => Ok(None),
-        }
+        HttpContentRange::from_header_map(&parts.headers)
     }
 }
 
-impl Display for HttpContentRange {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl HttpContentRange {
+    /// Writes this content range with `unit` as its range unit.
+    pub(crate) fn fmt_with_unit(&self, unit: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             HttpContentRange::Bound(Bound { range, size }) => match size {
-                Some(size) => write!(f, "{UNIT} {range}/{size}"),
-                None => write!(f, "{UNIT} {range}/*"),
+                Some(size) => write!(f, "{unit} {range}/{size}"),
+                None => write!(f, "{unit} {range}/*"),
             },
-            HttpContentRange::Unsatisfiable(Unsatisfiable { size }) => write!(f, "{UNIT} */{size}"),
+            HttpContentRange::Unsatisfiable(Unsatisfiable { size }) => write!(f, "{unit} */{size}"),
         }
     }
 }
+
+impl Display for HttpContentRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_unit(UNIT, f)
+    }
+}
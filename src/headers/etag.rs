@@ -0,0 +1,122 @@
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+#[cfg(feature = "http")]
+use http::HeaderValue;
+
+/// A typed HTTP entity-tag, as found in the `ETag`, `If-Range`, `If-Match`
+/// and `If-None-Match` headers.
+///
+/// Per [RFC 9110 Section 8.8.3], an entity-tag is an opaque quoted string,
+/// optionally prefixed by `W/` to mark it as weak.
+///
+/// [RFC 9110 Section 8.8.3]: https://www.rfc-editor.org/rfc/rfc9110#section-8.8.3
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ETag {
+    weak: bool,
+    opaque_tag: String,
+}
+
+impl ETag {
+    /// Returns `true` if this is a weak entity-tag (`W/"..."`).
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// Returns the opaque tag, without the quotes and the weakness indicator.
+    pub fn opaque_tag(&self) -> &str {
+        &self.opaque_tag
+    }
+
+    /// Performs a strong comparison, as required by `If-Range` and `If-Match`.
+    ///
+    /// Per [RFC 9110 Section 8.8.3.2], two entity-tags are strongly equivalent if
+    /// both are __not__ weak and their opaque-tags match character by character.
+    ///
+    /// [RFC 9110 Section 8.8.3.2]: https://www.rfc-editor.org/rfc/rfc9110#section-8.8.3.2
+    pub fn strong_eq(&self, other: &ETag) -> bool {
+        !self.weak && !other.weak && self.opaque_tag == other.opaque_tag
+    }
+
+    /// Performs a weak comparison, as required by `If-None-Match`.
+    ///
+    /// Per [RFC 9110 Section 8.8.3.2], two entity-tags are weakly equivalent if
+    /// their opaque-tags match character by character, regardless of either or
+    /// both being tagged as weak.
+    ///
+    /// [RFC 9110 Section 8.8.3.2]: https://www.rfc-editor.org/rfc/rfc9110#section-8.8.3.2
+    pub fn weak_eq(&self, other: &ETag) -> bool {
+        self.opaque_tag == other.opaque_tag
+    }
+}
+
+impl FromStr for ETag {
+    type Err = InvalidETag;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (weak, quoted) = match s.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, s),
+        };
+
+        let opaque_tag = quoted
+            .strip_prefix('"')
+            .and_then(|quoted| quoted.strip_suffix('"'))
+            .ok_or(InvalidETag)?;
+        // etagc = %x21 / %x23-7E / obs-text, where obs-text is not supported.
+        if !opaque_tag
+            .bytes()
+            .all(|b| b == 0x21 || (0x23..=0x7E).contains(&b))
+        {
+            return Err(InvalidETag);
+        }
+
+        Ok(Self {
+            weak,
+            opaque_tag: opaque_tag.to_owned(),
+        })
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<&ETag> for HeaderValue {
+    fn from(value: &ETag) -> Self {
+        HeaderValue::from_maybe_shared(value.to_string())
+            .expect("`ETag` Display produced non-visible ASCII characters")
+    }
+}
+
+#[cfg(feature = "http")]
+impl TryFrom<&HeaderValue> for ETag {
+    type Error = InvalidETag;
+
+    fn try_from(value: &HeaderValue) -> Result<Self, Self::Error> {
+        value.to_str().map_err(|_| InvalidETag)?.parse::<Self>()
+    }
+}
+
+#[cfg(feature = "http")]
+impl TryFrom<HeaderValue> for ETag {
+    type Error = InvalidETag;
+
+    fn try_from(value: HeaderValue) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+/// An error returned when parsing an entity-tag fails.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid entity-tag")]
+pub struct InvalidETag;
+
+impl Display for ETag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            f.write_str("W/")?;
+        }
+        write!(f, "\"{}\"", self.opaque_tag)
+    }
+}
@@ -4,7 +4,7 @@ use std::str::FromStr;
 
 use http::HeaderValue;
 
-use crate::headers::range::HttpRange;
+use crate::headers::{etag::ETag, range::HttpRange};
 
 /// A typed HTTP `If-Range` header.
 ///
@@ -59,22 +59,14 @@ impl IfRange {
     }
 }
 
-/// Performs a strong comparison of two entity-tags.
+/// Performs a strong comparison of two entity-tags, see [`ETag::strong_eq`].
 ///
-/// Per [RFC 9110 Section 8.8.3.2], two entity-tags are strongly equivalent if
-/// both are **not** weak and their opaque-tags match character by character.
-///
-/// [RFC 9110 Section 8.8.3.2]: https://www.rfc-editor.org/rfc/rfc9110#section-8.8.3.2
+/// Values that are not valid entity-tags never match.
 fn strong_etag_eq(a: &HeaderValue, b: &HeaderValue) -> bool {
-    let a = a.as_bytes();
-    let b = b.as_bytes();
-
-    // Weak tags (W/"...") never match in a strong comparison
-    if a.starts_with(b"W/") || b.starts_with(b"W/") {
-        return false;
+    match (ETag::try_from(a), ETag::try_from(b)) {
+        (Ok(a), Ok(b)) => a.strong_eq(&b),
+        _ => false,
     }
-
-    a == b
 }
 
 impl FromStr for IfRange {
@@ -106,6 +98,14 @@ impl TryFrom<&HeaderValue> for IfRange {
     }
 }
 
+impl TryFrom<HeaderValue> for IfRange {
+    type Error = InvalidIfRange;
+
+    fn try_from(value: HeaderValue) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
 /// An error returned when parsing an `If-Range` header fails.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 #[error("Invalid If-Range header")]
@@ -1,45 +1,150 @@
 use std::{
     fmt::{self, Display},
-    num::ParseIntError,
-    ops::RangeInclusive,
+    num::{NonZeroU64, ParseIntError},
+    ops::{Range, RangeInclusive},
 };
 
+#[cfg(feature = "http")]
+use http::{HeaderMap, HeaderName, HeaderValue};
+
+pub mod accept_ranges;
 pub mod content_range;
+pub mod etag;
+#[cfg(feature = "http")]
 pub mod if_range;
 pub mod range;
+pub mod range_or_content_range;
+pub mod range_unit;
+pub mod ranges;
 #[cfg(test)]
+// Reversed ranges are built on purpose, to check that they are rejected.
+#[allow(clippy::reversed_empty_ranges)]
 mod tests;
 
-const UNIT: &str = "bytes";
+const UNIT: &str = crate::BYTES_UNIT;
+
+/// The maximum length, in bytes, of a `Range` header value accepted by the
+/// [`FromStr`] implementations, unless a different limit is provided.
+///
+/// [`FromStr`]: std::str::FromStr
+pub const DEFAULT_MAX_HEADER_LEN: usize = 8 * 1024;
+
+/// How strictly a header value is parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ParseMode {
+    /// Only accepts canonical values.
+    Strict,
+    /// Follows the RFC grammar.
+    Standard,
+    /// Tolerates common client mistakes.
+    Lenient,
+}
+
+/// The header a [`ParseHttpRangeOrContentRangeError`] originated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderKind {
+    /// The `Range` request header.
+    Range,
+    /// The `Content-Range` response header.
+    ContentRange,
+}
+
+impl Display for HeaderKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HeaderKind::Range => f.write_str("Range"),
+            HeaderKind::ContentRange => f.write_str("Content-Range"),
+        }
+    }
+}
 
 /// The Errors that may occur during [`HttpContentRange`] and [`HttpRange`] parsing.
 ///
+/// Every variant carries the [`HeaderKind`] being parsed, so that the
+/// `Display` output names the offending header, and is available through
+/// [`ParseHttpRangeOrContentRangeError::header_kind`].
+///
+/// This enum is `#[non_exhaustive]`, since new kinds of errors may be detected:
+/// a `match` on it needs a wildcard arm.
+///
 /// [`HttpRange`]: crate::headers::range::HttpRange
 /// [`HttpContentRange`]: crate::headers::content_range::HttpContentRange
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
 pub enum ParseHttpRangeOrContentRangeError {
-    #[error("The header value is malformed")]
-    Malformed,
-    #[error("Contains nonvisible ASCII")]
-    ContainsNonVisibleASCII,
-    #[error("Empty header value")]
-    Empty,
-    #[error("Invalid unit")]
-    InvalidUnit,
-    #[error("Invalid range value")]
-    MalformedRange,
-    #[error(transparent)]
-    UnorderedRange(#[from] InvalidOrderedRange),
-    #[error("Invalid range piece")]
-    InvalidRangePiece(#[source] InvalidHttpU64),
-    #[error("Invalid size value")]
-    InvalidSize(#[source] InvalidHttpU64),
+    #[error("malformed {0} header value")]
+    Malformed(HeaderKind),
+    #[error("{0} header value contains nonvisible ASCII")]
+    ContainsNonVisibleASCII(HeaderKind),
+    #[error("empty {0} header value")]
+    Empty(HeaderKind),
+    #[error("invalid unit in {0} header value")]
+    InvalidUnit(HeaderKind),
+    #[error("invalid range value in {0} header value")]
+    MalformedRange(HeaderKind),
+    #[error("unordered range in {0} header value")]
+    UnorderedRange(HeaderKind, #[source] InvalidOrderedRange),
+    #[error("invalid range piece in {0} header value")]
+    InvalidRangePiece(HeaderKind, #[source] InvalidHttpU64),
+    #[error("invalid size value in {0} header value")]
+    InvalidSize(HeaderKind, #[source] InvalidHttpU64),
+    #[error("missing `/` before the size in {0} header value")]
+    MissingSizeDelimiter(HeaderKind),
+    #[error("{0} header value is longer than {1} bytes")]
+    TooLong(HeaderKind, usize),
+    #[error("unexpected `-` in a range of the {0} header value")]
+    ExtraDash(HeaderKind),
+    #[error("multiple {0} headers")]
+    MultipleHeaders(HeaderKind),
+    #[error("range length overflows u64 in {0} header value")]
+    LengthOverflow(HeaderKind),
+    #[error("invalid range value in {0} header value: digit separators are not allowed")]
+    DigitSeparator(HeaderKind),
+    #[error("range starts beyond the size in {0} header value")]
+    StartBeyondSize(HeaderKind),
+    #[error("{0} header value has more than {1} ranges")]
+    TooManyRanges(HeaderKind, usize),
+}
+
+impl ParseHttpRangeOrContentRangeError {
+    /// Returns the [`HeaderKind`] that failed to parse.
+    pub fn header_kind(&self) -> HeaderKind {
+        match self {
+            Self::Malformed(kind)
+            | Self::ContainsNonVisibleASCII(kind)
+            | Self::Empty(kind)
+            | Self::InvalidUnit(kind)
+            | Self::MalformedRange(kind)
+            | Self::UnorderedRange(kind, _)
+            | Self::InvalidRangePiece(kind, _)
+            | Self::InvalidSize(kind, _)
+            | Self::MissingSizeDelimiter(kind)
+            | Self::TooLong(kind, _)
+            | Self::ExtraDash(kind)
+            | Self::MultipleHeaders(kind)
+            | Self::LengthOverflow(kind)
+            | Self::DigitSeparator(kind)
+            | Self::StartBeyondSize(kind)
+            | Self::TooManyRanges(kind, _) => *kind,
+        }
+    }
 }
 
 #[cfg(feature = "axum")]
 impl axum_core::response::IntoResponse for ParseHttpRangeOrContentRangeError {
+    /// Responds with `400 Bad Request`.
+    ///
+    /// The body is empty, unless the `verbose-errors` feature is enabled, in which
+    /// case it contains the error message as plain text.
     fn into_response(self) -> axum_core::response::Response {
-        http::StatusCode::BAD_REQUEST.into_response()
+        #[cfg(feature = "verbose-errors")]
+        {
+            (http::StatusCode::BAD_REQUEST, self.to_string()).into_response()
+        }
+        #[cfg(not(feature = "verbose-errors"))]
+        {
+            http::StatusCode::BAD_REQUEST.into_response()
+        }
     }
 }
 
@@ -60,8 +165,17 @@ pub struct InvalidOrderedRange {
     end: u64,
 }
 
+/// An error that may occur when creating an [`OrderedRange`] from a half-open range.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidHalfOpenRange {
+    #[error("The provided range is empty")]
+    Empty,
+    #[error(transparent)]
+    Unordered(#[from] InvalidOrderedRange),
+}
+
 /// An ordered range.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct OrderedRange {
     start: u64,
     end: u64,
@@ -79,15 +193,85 @@ impl OrderedRange {
         Ok(Self { start, end })
     }
 
+    /// Creates a new [`OrderedRange`] from a half-open range, such as the one used
+    /// to slice a body.
+    ///
+    /// `start..end` is converted to `start..=end - 1`, so an empty range returns
+    /// [`InvalidHalfOpenRange::Empty`] and an inverted one returns
+    /// [`InvalidHalfOpenRange::Unordered`]. Since `end` is excluded, a range ending
+    /// at `u64::MAX` cannot be created this way.
+    pub fn from_half_open(range: Range<u64>) -> Result<Self, InvalidHalfOpenRange> {
+        if range.start > range.end {
+            return Err(InvalidOrderedRange {
+                start: range.start,
+                end: range.end,
+            }
+            .into());
+        }
+        if range.is_empty() {
+            return Err(InvalidHalfOpenRange::Empty);
+        }
+
+        Ok(Self {
+            start: range.start,
+            end: range.end - 1,
+        })
+    }
+
+    /// Creates a new [`OrderedRange`] in a `const` context, without checking that
+    /// `start` is less than or equal to `end`.
+    ///
+    /// This is not `unsafe`: an unordered range cannot cause undefined behavior,
+    /// but it breaks the invariant relied upon by the rest of the crate, which may
+    /// then produce wrong results or panic. The order is checked in debug builds.
+    pub const fn new_unchecked(start: u64, end: u64) -> Self {
+        debug_assert!(start <= end, "`start` is greater than `end`");
+
+        Self { start, end }
+    }
+
     /// Returns the inclusive starting point of the range.
-    pub fn start(&self) -> u64 {
+    pub const fn start(&self) -> u64 {
         self.start
     }
 
     /// Returns the inclusive ending point of the range.
-    pub fn end(&self) -> u64 {
+    pub const fn end(&self) -> u64 {
         self.end
     }
+
+    /// Returns `true` if the range starts at a multiple of `block`, such as the
+    /// block size of a storage backend.
+    pub fn is_aligned(&self, block: NonZeroU64) -> bool {
+        self.start % block == 0
+    }
+
+    /// Returns the inclusive `(start, end)` points of the range.
+    pub const fn into_tuple(self) -> (u64, u64) {
+        (self.start, self.end)
+    }
+
+    /// Clamps the end of the range to the last byte of a representation of `size`
+    /// bytes, as done when serving a range that extends past the end.
+    ///
+    /// Returns `None` if the range starts at or after `size`.
+    pub fn clamp_to_size(self, size: NonZeroU64) -> Option<OrderedRange> {
+        let last = size.get() - 1;
+        (self.start <= last).then_some(Self {
+            start: self.start,
+            end: self.end.min(last),
+        })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for OrderedRange {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let start = u64::arbitrary(u)?;
+        let end = u.int_in_range(start..=u64::MAX)?;
+
+        Ok(Self { start, end })
+    }
 }
 
 impl Display for OrderedRange {
@@ -96,6 +280,33 @@ impl Display for OrderedRange {
     }
 }
 
+/// Returns the only value of the `name` header, or `None` if it is absent.
+///
+/// A repeated header is rejected instead of silently using its first value,
+/// since intermediaries may disagree on which one applies.
+#[cfg(feature = "http")]
+pub(crate) fn single_header_value(
+    map: &HeaderMap,
+    name: HeaderName,
+    kind: HeaderKind,
+) -> Result<Option<&HeaderValue>, ParseHttpRangeOrContentRangeError> {
+    let mut values = map.get_all(name).into_iter();
+    let value = values.next();
+    if values.next().is_some() {
+        return Err(ParseHttpRangeOrContentRangeError::MultipleHeaders(kind));
+    }
+
+    Ok(value)
+}
+
+/// Returns `true` if `b` is a `tchar`, one of the characters of a `token` as
+/// defined by [RFC 9110 Section 5.6.2].
+///
+/// [RFC 9110 Section 5.6.2]: https://www.rfc-editor.org/rfc/rfc9110#section-5.6.2
+pub(crate) fn is_tchar(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b)
+}
+
 pub(crate) fn u64_unprefixed_parse(s: &str) -> Result<u64, InvalidHttpU64> {
     if s.starts_with("+") {
         Err(InvalidHttpU64::HasSignPrefix(s.to_owned()))
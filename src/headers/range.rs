@@ -1,70 +1,477 @@
-#[cfg(feature = "axum")]
-use std::convert::Infallible;
 use std::{
     fmt::{self, Display},
+    num::{NonZeroU64, NonZeroUsize},
+    ops::RangeInclusive,
     str::FromStr,
 };
 
-use http::HeaderValue;
+#[cfg(feature = "http")]
+use http::{HeaderMap, HeaderName, HeaderValue};
+
+use crate::headers::{
+    DEFAULT_MAX_HEADER_LEN, HeaderKind, InvalidOrderedRange, OrderedRange,
+    ParseHttpRangeOrContentRangeError, ParseMode, UNIT,
+    range_unit::{RangeUnit, WithUnit},
+    u64_unprefixed_parse,
+};
 
-use crate::headers::{OrderedRange, ParseHttpRangeOrContentRangeError, UNIT, u64_unprefixed_parse};
+const HEADER_KIND: HeaderKind = HeaderKind::Range;
 
 /// A typed HTTP `Range` header that only supports a __single__ range.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// This enum is `#[non_exhaustive]`: a `match` on it needs a wildcard arm.
+/// Code that does not need the variants can use the constructors, such as
+/// [`HttpRange::range`], and the predicates, such as [`HttpRange::is_suffix`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[non_exhaustive]
 pub enum HttpRange {
+    /// `bytes=start-`
     StartingPoint(u64),
+    /// `bytes=start-end`
     Range(OrderedRange),
+    /// `bytes=-suffix`, the last `suffix` bytes of the representation.
+    ///
+    /// A leading `-` always introduces a suffix, since positions cannot be
+    /// negative: `bytes=-5-10` is rejected instead of being read as a range
+    /// starting at `-5`.
     Suffix(u64),
 }
 
-impl FromStr for HttpRange {
-    type Err = ParseHttpRangeOrContentRangeError;
+impl HttpRange {
+    /// Extracts and parses the `Range` header from a [`HeaderMap`].
+    ///
+    /// Returns `Ok(None)` if the header is absent, and an error if it is
+    /// present more than once.
+    #[cfg(feature = "http")]
+    pub fn from_header_map(
+        map: &HeaderMap,
+    ) -> Result<Option<Self>, ParseHttpRangeOrContentRangeError> {
+        crate::headers::single_header_value(map, http::header::RANGE, HEADER_KIND)?
+            .map(Self::try_from)
+            .transpose()
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let s = s.trim();
-        if s.is_empty() {
-            return Err(ParseHttpRangeOrContentRangeError::Empty);
+    /// Returns the `Range` header name and value, ready to be inserted into a [`HeaderMap`].
+    #[cfg(feature = "http")]
+    pub fn as_header_pair(&self) -> (HeaderName, HeaderValue) {
+        (http::header::RANGE, HeaderValue::from(self))
+    }
+
+    /// Parses a `Range` header value, tolerating a missing or empty unit and
+    /// trailing parameters.
+    ///
+    /// Some clients send `=0-99` or `0-99` without the `bytes` unit. This is
+    /// malformed per [RFC 9110 Section 14.1.1], so [`FromStr`] rejects it, but
+    /// this method assumes `bytes` instead.
+    ///
+    /// Likewise, the `Range` header has no parameters, so in a nonstandard value
+    /// such as `bytes=0-99;q=0.5` everything after the first `;` is discarded.
+    ///
+    /// [RFC 9110 Section 14.1.1]: https://www.rfc-editor.org/rfc/rfc9110#section-14.1.1
+    pub fn parse_lenient(s: &str) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        Self::parse(s, ParseMode::Lenient, DEFAULT_MAX_HEADER_LEN)
+    }
+
+    /// Parses a `Range` header value, rejecting range values with leading zeros.
+    ///
+    /// [`FromStr`] accepts values such as `bytes=007-0099` for compatibility,
+    /// while this method only accepts canonical decimal integers, where the
+    /// single digit `0` is the only value allowed to start with a zero.
+    ///
+    /// It also rejects `bytes=0-18446744073709551615` with [`LengthOverflow`],
+    /// since the length of that range does not fit in a `u64`.
+    ///
+    /// [`LengthOverflow`]: ParseHttpRangeOrContentRangeError::LengthOverflow
+    pub fn parse_strict(s: &str) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        Self::parse(s, ParseMode::Strict, DEFAULT_MAX_HEADER_LEN)
+    }
+
+    /// Parses a `Range` header value from raw bytes, like [`FromStr`].
+    ///
+    /// Bytes that are not visible ASCII, as defined by [`HeaderValue::to_str`],
+    /// are rejected with [`ContainsNonVisibleASCII`], so callers working on byte
+    /// buffers do not need to validate UTF-8 first.
+    ///
+    /// [`ContainsNonVisibleASCII`]: ParseHttpRangeOrContentRangeError::ContainsNonVisibleASCII
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        if !bytes
+            .iter()
+            .all(|&b| b == b'\t' || (0x20..0x7F).contains(&b))
+        {
+            return Err(ParseHttpRangeOrContentRangeError::ContainsNonVisibleASCII(
+                HEADER_KIND,
+            ));
         }
 
-        let (unit_str, range_str) = s
-            .split_once("=")
-            .ok_or(ParseHttpRangeOrContentRangeError::Malformed)?;
-        // Range unit names are case-insensitive (RFC 9110 Section 14.1).
-        if !unit_str.eq_ignore_ascii_case(UNIT) {
-            return Err(ParseHttpRangeOrContentRangeError::InvalidUnit);
+        let s = std::str::from_utf8(bytes).expect("visible ASCII is valid UTF-8");
+        s.parse()
+    }
+
+    /// Parses a `Range` header value like [`FromStr`], but rejects values longer
+    /// than `max_len` bytes instead of [`DEFAULT_MAX_HEADER_LEN`].
+    ///
+    /// [`DEFAULT_MAX_HEADER_LEN`]: crate::headers::DEFAULT_MAX_HEADER_LEN
+    pub fn parse_with_max_len(
+        s: &str,
+        max_len: usize,
+    ) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        Self::parse(s, ParseMode::Standard, max_len)
+    }
+
+    /// Parses a `Range` header value like [`FromStr`], but accepts any range unit
+    /// and keeps it with the range, instead of rejecting units other than `bytes`
+    /// with [`InvalidUnit`].
+    ///
+    /// The range is parsed with the `int-range` and `suffix-range` syntax of the
+    /// `bytes` unit, whatever the unit. [`FromStr`] and [`HttpRange::parse_strict`]
+    /// keep rejecting other units.
+    ///
+    /// [`InvalidUnit`]: ParseHttpRangeOrContentRangeError::InvalidUnit
+    pub fn parse_with_unit(s: &str) -> Result<WithUnit<Self>, ParseHttpRangeOrContentRangeError> {
+        let (unit, range_str) = strip_any_unit(s)?;
+
+        Ok(WithUnit::new(
+            unit,
+            parse_range_spec(range_str, ParseMode::Standard)?,
+        ))
+    }
+
+    /// Returns an iterator over the byte indices covered by this range in a
+    /// representation of `size` bytes.
+    ///
+    /// The iterator is empty if the range is unsatisfiable.
+    pub fn byte_indices(self, size: NonZeroU64) -> impl Iterator<Item = u64> {
+        self.resolve(size.get())
+            .into_iter()
+            .flat_map(|range| range.start()..=range.end())
+    }
+
+    /// Creates a [`HttpRange::StartingPoint`], usable in a `const` context.
+    pub const fn starting_point(start: u64) -> Self {
+        HttpRange::StartingPoint(start)
+    }
+
+    /// Creates a [`HttpRange::Suffix`], usable in a `const` context.
+    pub const fn suffix(suffix: u64) -> Self {
+        HttpRange::Suffix(suffix)
+    }
+
+    /// Creates a [`HttpRange::Range`], returning [`InvalidOrderedRange`] if the
+    /// start of `range` is greater than its end.
+    pub fn range(range: RangeInclusive<u64>) -> Result<Self, InvalidOrderedRange> {
+        OrderedRange::new(range).map(HttpRange::Range)
+    }
+
+    /// Returns `true` if this is a [`HttpRange::StartingPoint`].
+    pub fn is_starting_point(&self) -> bool {
+        matches!(self, HttpRange::StartingPoint(_))
+    }
+
+    /// Returns `true` if this is a [`HttpRange::Range`].
+    pub fn is_range(&self) -> bool {
+        matches!(self, HttpRange::Range(_))
+    }
+
+    /// Returns `true` if this is a [`HttpRange::Suffix`].
+    pub fn is_suffix(&self) -> bool {
+        matches!(self, HttpRange::Suffix(_))
+    }
+
+    /// Creates a [`HttpRange::Range`] covering the `start_frac..end_frac` fraction of a
+    /// representation of `size` bytes, for instance to seek to a percentage of a media file.
+    ///
+    /// The fractions are clamped to `[0, 1]` and the byte offsets are rounded down,
+    /// so `0.5..0.75` of 1000 bytes is `bytes=500-749`.
+    pub fn from_fraction(
+        start_frac: f64,
+        end_frac: f64,
+        size: NonZeroU64,
+    ) -> Result<Self, InvalidFraction> {
+        if start_frac.is_nan() || end_frac.is_nan() {
+            return Err(InvalidFraction::NotANumber);
+        }
+        if start_frac > end_frac {
+            return Err(InvalidFraction::Unordered {
+                start: start_frac,
+                end: end_frac,
+            });
+        }
+
+        // `as` saturates, and the offsets are clamped to `size` anyway.
+        let offset =
+            |frac: f64| ((frac.clamp(0.0, 1.0) * size.get() as f64) as u64).min(size.get());
+        let (start, end) = (offset(start_frac), offset(end_frac));
+        if start >= end {
+            return Err(InvalidFraction::Empty);
         }
 
-        let (start_str, end_str) = range_str
-            .split_once("-")
-            .ok_or(ParseHttpRangeOrContentRangeError::MalformedRange)?;
+        Ok(HttpRange::Range(OrderedRange {
+            start,
+            end: end - 1,
+        }))
+    }
 
-        match (start_str.is_empty(), end_str.is_empty()) {
-            (false, false) => {
-                let start = u64_unprefixed_parse(start_str)
-                    .map_err(ParseHttpRangeOrContentRangeError::InvalidRangePiece)?;
-                let end = u64_unprefixed_parse(end_str)
-                    .map_err(ParseHttpRangeOrContentRangeError::InvalidRangePiece)?;
+    /// Splits a representation of `size` bytes into `parts` contiguous, non-overlapping
+    /// ranges covering it entirely, for instance to download it in parallel.
+    ///
+    /// Every range has the same length, except the last one which also covers the
+    /// remainder. If `size` is smaller than `parts`, `size` ranges of one byte each
+    /// are returned.
+    pub fn split(size: NonZeroU64, parts: NonZeroUsize) -> Vec<Self> {
+        let size = size.get();
+        let parts = u64::try_from(parts.get())
+            .expect("we do not support 128bit usize")
+            .min(size);
+        let part_len = size / parts;
 
-                let range = OrderedRange::new(start..=end)?;
-                Ok(Self::Range(range))
-            }
-            (false, true) => {
-                let start = u64_unprefixed_parse(start_str)
-                    .map_err(ParseHttpRangeOrContentRangeError::InvalidRangePiece)?;
+        (0..parts)
+            .map(|part| {
+                let start = part * part_len;
+                let end = if part == parts - 1 {
+                    size - 1
+                } else {
+                    start + part_len - 1
+                };
+                HttpRange::Range(OrderedRange { start, end })
+            })
+            .collect()
+    }
 
-                Ok(Self::StartingPoint(start))
-            }
-            (true, false) => {
-                let suffix = u64_unprefixed_parse(end_str)
-                    .map_err(ParseHttpRangeOrContentRangeError::InvalidRangePiece)?;
+    /// Returns `true` if this range is satisfiable for a representation of `size` bytes.
+    ///
+    /// This is the same check as [`file_range`], without building a [`ContentRange`].
+    ///
+    /// [`file_range`]: crate::file_range
+    /// [`ContentRange`]: crate::ContentRange
+    pub fn is_satisfiable(self, size: NonZeroU64) -> bool {
+        self.resolve(size.get()).is_some()
+    }
+
+    /// Rewrites this range as the absolute [`OrderedRange`] it covers in a
+    /// representation of `size` bytes, clamping the end to the last byte.
+    ///
+    /// For instance `bytes=0-` and `bytes=-size` both normalize to `0..=size - 1`,
+    /// and a suffix `bytes=-n` normalizes to `size - n..=size - 1`, clamped to the
+    /// whole representation when `n` is larger than `size`. Returns `None` if the
+    /// range is unsatisfiable.
+    ///
+    /// This is the resolution [`file_range`] relies on, so it can be used to
+    /// resolve a range independently of the serving functions.
+    ///
+    /// [`file_range`]: crate::file_range
+    #[doc(alias = "to_ordered")]
+    pub fn normalize(self, size: NonZeroU64) -> Option<OrderedRange> {
+        self.resolve(size.get())
+    }
+
+    /// Checks whether this range and `other` request the same bytes of a
+    /// representation of `size` bytes, such as `bytes=0-99` and `bytes=-100` for
+    /// a 100 bytes representation.
+    ///
+    /// Unsatisfiable ranges request no bytes, so they never match.
+    pub fn same_bytes(&self, other: &HttpRange, size: NonZeroU64) -> bool {
+        match (self.normalize(size), other.normalize(size)) {
+            (Some(range), Some(other)) => range == other,
+            _ => false,
+        }
+    }
 
-                Ok(Self::Suffix(suffix))
+    /// Like [`HttpRange::normalize`], but snaps the start of the resolved range down
+    /// to the nearest multiple of `block`, extending the range.
+    ///
+    /// This is useful for block-oriented backends that cannot read from an
+    /// arbitrary offset, see [`OrderedRange::is_aligned`]. The end is kept as is,
+    /// so the caller still needs to skip the extra bytes before serving.
+    /// Returns `None` if the range is unsatisfiable.
+    pub fn align_down(self, block: NonZeroU64, size: NonZeroU64) -> Option<OrderedRange> {
+        let range = self.normalize(size)?;
+
+        Some(OrderedRange {
+            start: range.start - range.start % block,
+            end: range.end,
+        })
+    }
+
+    /// Resolves this range against a representation of `size` bytes, clamping
+    /// the end to the last byte. Returns `None` if the range is unsatisfiable.
+    pub(crate) fn resolve(self, size: u64) -> Option<OrderedRange> {
+        let last = size.checked_sub(1)?;
+        let (start, end) = match self {
+            HttpRange::StartingPoint(start) => (start, last),
+            HttpRange::Range(range) => return range.clamp_to_size(NonZeroU64::new(size)?),
+            HttpRange::Suffix(0) => return None,
+            HttpRange::Suffix(suffix) => (size.saturating_sub(suffix), last),
+        };
+
+        (start <= last).then_some(OrderedRange { start, end })
+    }
+
+    fn parse(
+        s: &str,
+        mode: ParseMode,
+        max_len: usize,
+    ) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        let range_str = strip_unit(s, mode, max_len)?;
+        parse_range_spec(range_str, mode)
+    }
+}
+
+/// Validates the length and the unit of a `Range` header value and returns the
+/// range set that follows it.
+pub(crate) fn strip_unit(
+    s: &str,
+    mode: ParseMode,
+    max_len: usize,
+) -> Result<&str, ParseHttpRangeOrContentRangeError> {
+    let (unit_str, range_str) = split_unit(s, mode, max_len)?;
+    // Range unit names are case-insensitive (RFC 9110 Section 14.1).
+    if !unit_str.is_empty() && !unit_str.eq_ignore_ascii_case(UNIT) {
+        return Err(ParseHttpRangeOrContentRangeError::InvalidUnit(HEADER_KIND));
+    }
+
+    check_range_set(range_str)
+}
+
+/// Like [`strip_unit`], but returns the unit, whatever it is, instead of only
+/// accepting `bytes`.
+pub(crate) fn strip_any_unit(
+    s: &str,
+) -> Result<(RangeUnit, &str), ParseHttpRangeOrContentRangeError> {
+    let (unit_str, range_str) = split_unit(s, ParseMode::Standard, DEFAULT_MAX_HEADER_LEN)?;
+    let unit = RangeUnit::from_token(unit_str, HEADER_KIND)?;
+
+    Ok((unit, check_range_set(range_str)?))
+}
+
+/// Validates the length of a `Range` header value and splits it into the unit,
+/// which can only be empty in lenient mode, and the range set.
+fn split_unit(
+    s: &str,
+    mode: ParseMode,
+    max_len: usize,
+) -> Result<(&str, &str), ParseHttpRangeOrContentRangeError> {
+    // Checked before doing any work on the value.
+    if s.len() > max_len {
+        return Err(ParseHttpRangeOrContentRangeError::TooLong(
+            HEADER_KIND,
+            max_len,
+        ));
+    }
+
+    let mut s = s.trim();
+    if mode == ParseMode::Lenient {
+        // Parameters are not part of the grammar, so they are discarded.
+        if let Some((range_set, _params)) = s.split_once(';') {
+            s = range_set.trim_end();
+        }
+    }
+    if s.is_empty() {
+        return Err(ParseHttpRangeOrContentRangeError::Empty(HEADER_KIND));
+    }
+
+    let (unit_str, range_str) = match s.split_once("=") {
+        Some(split) => split,
+        None if mode == ParseMode::Lenient => ("", s),
+        None => {
+            return Err(ParseHttpRangeOrContentRangeError::Malformed(HEADER_KIND));
+        }
+    };
+    if unit_str.is_empty() && mode != ParseMode::Lenient {
+        return Err(ParseHttpRangeOrContentRangeError::Malformed(HEADER_KIND));
+    }
+
+    Ok((unit_str, range_str))
+}
+
+fn check_range_set(range_str: &str) -> Result<&str, ParseHttpRangeOrContentRangeError> {
+    // A `/` belongs to the `Content-Range` syntax, and only the unit is
+    // followed by a `=`.
+    if range_str.contains(['/', '=']) {
+        return Err(ParseHttpRangeOrContentRangeError::Malformed(HEADER_KIND));
+    }
+
+    Ok(range_str)
+}
+
+/// Parses a single range spec, such as `0-99`, `100-` or `-50`.
+pub(crate) fn parse_range_spec(
+    s: &str,
+    mode: ParseMode,
+) -> Result<HttpRange, ParseHttpRangeOrContentRangeError> {
+    let (start_str, end_str) =
+        s.split_once("-")
+            .ok_or(ParseHttpRangeOrContentRangeError::MalformedRange(
+                HEADER_KIND,
+            ))?;
+    // Such as `-5-10` or `5--10`, where a piece looks like a negative number.
+    // A `,` means that the dash belongs to another range, which is not an
+    // extra dash of this one.
+    if end_str.contains('-') && !end_str.contains(',') {
+        return Err(ParseHttpRangeOrContentRangeError::ExtraDash(HEADER_KIND));
+    }
+
+    match (start_str.is_empty(), end_str.is_empty()) {
+        (false, false) => {
+            let start = parse_range_piece(start_str, mode)?;
+            let end = parse_range_piece(end_str, mode)?;
+
+            let range = OrderedRange::new(start..=end).map_err(|err| {
+                ParseHttpRangeOrContentRangeError::UnorderedRange(HEADER_KIND, err)
+            })?;
+            // `0-18446744073709551615` is `u64::MAX + 1` bytes long.
+            if mode == ParseMode::Strict && end - start == u64::MAX {
+                return Err(ParseHttpRangeOrContentRangeError::LengthOverflow(
+                    HEADER_KIND,
+                ));
             }
-            (true, true) => Err(ParseHttpRangeOrContentRangeError::Malformed),
+            Ok(HttpRange::Range(range))
+        }
+        (false, true) => {
+            let start = parse_range_piece(start_str, mode)?;
+
+            Ok(HttpRange::StartingPoint(start))
+        }
+        (true, false) => {
+            let suffix = parse_range_piece(end_str, mode)?;
+
+            Ok(HttpRange::Suffix(suffix))
         }
+        (true, true) => Err(ParseHttpRangeOrContentRangeError::Malformed(HEADER_KIND)),
+    }
+}
+
+fn parse_range_piece(s: &str, mode: ParseMode) -> Result<u64, ParseHttpRangeOrContentRangeError> {
+    if mode == ParseMode::Strict && s.len() > 1 && s.starts_with('0') {
+        return Err(ParseHttpRangeOrContentRangeError::MalformedRange(
+            HEADER_KIND,
+        ));
+    }
+
+    // Values such as `1_000` are valid Rust literals, but not valid HTTP ints.
+    if s.contains('_')
+        && s.bytes().all(|b| b.is_ascii_digit() || b == b'_')
+        && s.starts_with(|c: char| c.is_ascii_digit())
+        && s.ends_with(|c: char| c.is_ascii_digit())
+    {
+        return Err(ParseHttpRangeOrContentRangeError::DigitSeparator(
+            HEADER_KIND,
+        ));
+    }
+
+    u64_unprefixed_parse(s)
+        .map_err(|err| ParseHttpRangeOrContentRangeError::InvalidRangePiece(HEADER_KIND, err))
+}
+
+impl FromStr for HttpRange {
+    type Err = ParseHttpRangeOrContentRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, ParseMode::Standard, DEFAULT_MAX_HEADER_LEN)
     }
 }
 
+#[cfg(feature = "http")]
 impl From<&HttpRange> for HeaderValue {
     fn from(value: &HttpRange) -> Self {
         HeaderValue::from_maybe_shared(value.to_string())
@@ -72,13 +479,20 @@ impl From<&HttpRange> for HeaderValue {
     }
 }
 
+#[cfg(feature = "http")]
 impl TryFrom<&HeaderValue> for HttpRange {
     type Error = ParseHttpRangeOrContentRangeError;
     fn try_from(value: &HeaderValue) -> Result<Self, Self::Error> {
-        value
-            .to_str()
-            .map_err(|_| ParseHttpRangeOrContentRangeError::ContainsNonVisibleASCII)?
-            .parse::<Self>()
+        Self::from_bytes(value.as_bytes())
+    }
+}
+
+#[cfg(feature = "http")]
+impl TryFrom<HeaderValue> for HttpRange {
+    type Error = ParseHttpRangeOrContentRangeError;
+
+    fn try_from(value: HeaderValue) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
     }
 }
 
@@ -87,7 +501,7 @@ impl<S> axum_core::extract::OptionalFromRequestParts<S> for HttpRange
 where
     S: Send + Sync,
 {
-    type Rejection = Infallible;
+    type Rejection = ParseHttpRangeOrContentRangeError;
 
     /// Extracts an optional [`HttpRange`] from the request's `Range` header.
     ///
@@ -98,6 +512,10 @@ where
     /// in all such cases instead of rejecting the request, so that the full
     /// representation is served.
     ///
+    /// Repeated `Range` headers are rejected with
+    /// [`ParseHttpRangeOrContentRangeError::MultipleHeaders`], which responds
+    /// with `400 Bad Request`.
+    ///
     /// [RFC 9110 Section 14.2]: https://www.rfc-editor.org/rfc/rfc9110#section-14.2
     async fn from_request_parts(
         parts: &mut http::request::Parts,
@@ -107,20 +525,38 @@ where
             return Ok(None);
         }
 
-        let range = parts
-            .headers
-            .get(http::header::RANGE)
-            .and_then(|range| HttpRange::try_from(range).ok());
-        Ok(range)
+        match HttpRange::from_header_map(&parts.headers) {
+            Ok(range) => Ok(range),
+            Err(err @ ParseHttpRangeOrContentRangeError::MultipleHeaders(_)) => Err(err),
+            Err(_) => Ok(None),
+        }
     }
 }
 
-impl Display for HttpRange {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+/// The Errors that may occur when creating a [`HttpRange`] with [`HttpRange::from_fraction`].
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+pub enum InvalidFraction {
+    #[error("The provided fraction is not a number")]
+    NotANumber,
+    #[error("The provided `start`: {start} is greater than `end`: {end}")]
+    Unordered { start: f64, end: f64 },
+    #[error("The provided fractions cover no byte")]
+    Empty,
+}
+
+impl HttpRange {
+    /// Writes this range with `unit` as its range unit.
+    pub(crate) fn fmt_with_unit(&self, unit: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            HttpRange::StartingPoint(start) => write!(f, "{UNIT}={start}-"),
-            HttpRange::Range(range) => write!(f, "{UNIT}={range}"),
-            HttpRange::Suffix(suffix) => write!(f, "{UNIT}=-{suffix}"),
+            HttpRange::StartingPoint(start) => write!(f, "{unit}={start}-"),
+            HttpRange::Range(range) => write!(f, "{unit}={range}"),
+            HttpRange::Suffix(suffix) => write!(f, "{unit}=-{suffix}"),
         }
     }
 }
+
+impl Display for HttpRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_unit(UNIT, f)
+    }
+}
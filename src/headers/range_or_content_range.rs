@@ -0,0 +1,37 @@
+use std::str::FromStr;
+
+use crate::headers::{
+    HeaderKind, ParseHttpRangeOrContentRangeError, content_range::HttpContentRange,
+    range::HttpRange,
+};
+
+/// Either a `Range` or a `Content-Range` header value.
+///
+/// Useful when inspecting a header value of unknown origin: the two forms are
+/// told apart by the separator following the unit, `=` for `Range`
+/// (`bytes=0-99`) and a space for `Content-Range` (`bytes 0-99/200`). A value
+/// without either separator is reported as a malformed `Range`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RangeOrContentRange {
+    Range(HttpRange),
+    ContentRange(HttpContentRange),
+}
+
+impl FromStr for RangeOrContentRange {
+    type Err = ParseHttpRangeOrContentRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseHttpRangeOrContentRangeError::Empty(HeaderKind::Range));
+        }
+
+        match s.find(['=', ' ']).map(|index| &s[index..index + 1]) {
+            Some("=") => s.parse().map(Self::Range),
+            Some(_) => s.parse().map(Self::ContentRange),
+            None => Err(ParseHttpRangeOrContentRangeError::Malformed(
+                HeaderKind::Range,
+            )),
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt::{self, Display};
+
+#[cfg(feature = "http")]
+use http::HeaderValue;
+
+use crate::headers::{
+    HeaderKind, ParseHttpRangeOrContentRangeError, UNIT, content_range::HttpContentRange, is_tchar,
+    range::HttpRange, ranges::HttpRanges,
+};
+
+/// A range unit, as found in the `Range` and `Content-Range` headers.
+///
+/// Per [RFC 9110 Section 14.1], `bytes` is the only range unit defined by the
+/// specification, and range unit names are case-insensitive. The [`FromStr`] and
+/// strict parsers of the range types reject any other unit with [`InvalidUnit`],
+/// while [`HttpRange::parse_with_unit`], [`HttpRanges::parse_with_unit`] and
+/// [`HttpContentRange::parse_with_unit`] keep it with the parsed value in a
+/// [`WithUnit`], so that callers can decide how to handle it. [`RangeUnit::of_range`] and
+/// [`RangeUnit::of_content_range`] only inspect the unit of a header value.
+///
+/// [RFC 9110 Section 14.1]: https://www.rfc-editor.org/rfc/rfc9110#section-14.1
+/// [`FromStr`]: std::str::FromStr
+/// [`InvalidUnit`]: ParseHttpRangeOrContentRangeError::InvalidUnit
+/// [`HttpRange::parse_with_unit`]: crate::headers::range::HttpRange::parse_with_unit
+/// [`HttpRanges::parse_with_unit`]: crate::headers::ranges::HttpRanges::parse_with_unit
+/// [`HttpContentRange::parse_with_unit`]: crate::headers::content_range::HttpContentRange::parse_with_unit
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RangeUnit {
+    /// The `bytes` range unit.
+    Bytes,
+    /// Any other range unit, in lowercase.
+    Other(String),
+}
+
+impl RangeUnit {
+    /// Returns `true` if this is [`RangeUnit::Bytes`].
+    pub fn is_bytes(&self) -> bool {
+        matches!(self, RangeUnit::Bytes)
+    }
+
+    /// Returns the name of the range unit, in lowercase.
+    pub fn as_str(&self) -> &str {
+        match self {
+            RangeUnit::Bytes => UNIT,
+            RangeUnit::Other(unit) => unit,
+        }
+    }
+
+    /// Returns the range unit of a `Range` header value, such as `bytes=0-99`,
+    /// without parsing the ranges that follow it.
+    pub fn of_range(s: &str) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        Self::parse(s, '=', HeaderKind::Range)
+    }
+
+    /// Returns the range unit of a `Content-Range` header value, such as
+    /// `bytes 0-99/200`, without parsing the range that follows it.
+    pub fn of_content_range(s: &str) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        Self::parse(s, ' ', HeaderKind::ContentRange)
+    }
+
+    fn parse(
+        s: &str,
+        separator: char,
+        kind: HeaderKind,
+    ) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseHttpRangeOrContentRangeError::Empty(kind));
+        }
+
+        let (unit, _) = s
+            .split_once(separator)
+            .ok_or(ParseHttpRangeOrContentRangeError::Malformed(kind))?;
+        Self::from_token(unit, kind)
+    }
+
+    /// Returns the range unit named `unit`, which must be a `token`.
+    pub(crate) fn from_token(
+        unit: &str,
+        kind: HeaderKind,
+    ) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        // range-unit = token
+        if unit.is_empty() || !unit.bytes().all(is_tchar) {
+            return Err(ParseHttpRangeOrContentRangeError::InvalidUnit(kind));
+        }
+
+        if unit.eq_ignore_ascii_case(UNIT) {
+            Ok(RangeUnit::Bytes)
+        } else {
+            Ok(RangeUnit::Other(unit.to_ascii_lowercase()))
+        }
+    }
+}
+
+impl Display for RangeUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A parsed header value together with its range unit, as returned by the
+/// `parse_with_unit` functions.
+///
+/// Unlike the value alone, whose `Display` always writes `bytes`, it is written
+/// back with the parsed unit, so `items=0-9` round-trips as `items=0-9`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WithUnit<T> {
+    unit: RangeUnit,
+    value: T,
+}
+
+impl<T> WithUnit<T> {
+    /// Creates a new [`WithUnit`] from its range unit and value.
+    pub fn new(unit: RangeUnit, value: T) -> Self {
+        Self { unit, value }
+    }
+
+    /// Returns the range unit.
+    pub fn unit(&self) -> &RangeUnit {
+        &self.unit
+    }
+
+    /// Returns the parsed value.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the range unit and the parsed value.
+    pub fn into_parts(self) -> (RangeUnit, T) {
+        (self.unit, self.value)
+    }
+}
+
+impl Display for WithUnit<HttpRange> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_with_unit(self.unit.as_str(), f)
+    }
+}
+
+impl Display for WithUnit<HttpRanges> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_with_unit(self.unit.as_str(), f)
+    }
+}
+
+impl Display for WithUnit<HttpContentRange> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_with_unit(self.unit.as_str(), f)
+    }
+}
+
+#[cfg(feature = "http")]
+impl<T> From<&WithUnit<T>> for HeaderValue
+where
+    WithUnit<T>: Display,
+{
+    fn from(value: &WithUnit<T>) -> Self {
+        HeaderValue::from_maybe_shared(value.to_string())
+            .expect("`WithUnit` Display produced non-visible ASCII characters")
+    }
+}
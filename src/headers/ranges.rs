@@ -0,0 +1,340 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+    num::NonZeroU64,
+    str::FromStr,
+};
+
+use bytes::Bytes;
+#[cfg(feature = "http")]
+use http::HeaderValue;
+
+use crate::{
+    BodyRange, ContentRange, RangeConfig, UnsatisfiableRange, file_range,
+    headers::{
+        DEFAULT_MAX_HEADER_LEN, HeaderKind, OrderedRange, ParseHttpRangeOrContentRangeError,
+        ParseMode, UNIT,
+        range::{HttpRange, parse_range_spec, strip_any_unit, strip_unit},
+        range_unit::WithUnit,
+    },
+};
+
+const HEADER_KIND: HeaderKind = HeaderKind::Range;
+
+/// A typed HTTP `Range` header that supports __multiple__ ranges.
+///
+/// The ranges are kept in the order in which they were requested.
+///
+/// The [`FromStr`] implementation rejects empty list elements, such as the
+/// trailing one in `bytes=0-99,`. Use [`HttpRanges::parse_lenient`] to ignore them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HttpRanges(Vec<HttpRange>);
+
+impl HttpRanges {
+    /// Parses a `Range` header value, tolerating a missing or empty unit,
+    /// ignoring empty list elements and discarding nonstandard parameters.
+    ///
+    /// Values such as `bytes=0-99,` or `bytes=,0-99` are rejected by [`FromStr`],
+    /// while this method skips the empty pieces, as recipients of list-based
+    /// fields are expected to do per [RFC 9110 Section 5.6.1]. A value without
+    /// any range is still [`Malformed`]. Everything after the first `;` is
+    /// discarded, as in [`HttpRange::parse_lenient`].
+    ///
+    /// [RFC 9110 Section 5.6.1]: https://www.rfc-editor.org/rfc/rfc9110#section-5.6.1
+    /// [`Malformed`]: ParseHttpRangeOrContentRangeError::Malformed
+    pub fn parse_lenient(s: &str) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        Self::parse(s, ParseMode::Lenient, DEFAULT_MAX_HEADER_LEN)
+    }
+
+    /// Parses a `Range` header value, rejecting empty list elements, range
+    /// values with leading zeros and ranges whose length overflows a `u64`,
+    /// like [`HttpRange::parse_strict`].
+    pub fn parse_strict(s: &str) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        Self::parse(s, ParseMode::Strict, DEFAULT_MAX_HEADER_LEN)
+    }
+
+    /// Parses a `Range` header value like [`FromStr`], but rejects values longer
+    /// than `max_len` bytes instead of [`DEFAULT_MAX_HEADER_LEN`].
+    ///
+    /// This bounds the number of ranges, and the work done to parse them, for
+    /// values with thousands of ranges.
+    ///
+    /// [`DEFAULT_MAX_HEADER_LEN`]: crate::headers::DEFAULT_MAX_HEADER_LEN
+    pub fn parse_with_max_len(
+        s: &str,
+        max_len: usize,
+    ) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        Self::parse(s, ParseMode::Standard, max_len)
+    }
+
+    /// Parses a `Range` header value like [`FromStr`], but applies the
+    /// [`RangeConfig::max_header_len`] and [`RangeConfig::max_ranges`] limits.
+    ///
+    /// Values with more than [`RangeConfig::max_ranges`] ranges are rejected with
+    /// [`TooManyRanges`].
+    ///
+    /// [`TooManyRanges`]: ParseHttpRangeOrContentRangeError::TooManyRanges
+    pub fn parse_with_config(
+        s: &str,
+        config: &RangeConfig,
+    ) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        let ranges = Self::parse(s, ParseMode::Standard, config.max_header_len)?;
+        if ranges.len() > config.max_ranges {
+            return Err(ParseHttpRangeOrContentRangeError::TooManyRanges(
+                HEADER_KIND,
+                config.max_ranges,
+            ));
+        }
+
+        Ok(ranges)
+    }
+
+    /// Parses a `Range` header value like [`FromStr`], but accepts any range unit
+    /// and keeps it with the ranges, like [`HttpRange::parse_with_unit`].
+    pub fn parse_with_unit(s: &str) -> Result<WithUnit<Self>, ParseHttpRangeOrContentRangeError> {
+        let (unit, ranges_str) = strip_any_unit(s)?;
+
+        Ok(WithUnit::new(
+            unit,
+            Self::parse_range_set(ranges_str, ParseMode::Standard)?,
+        ))
+    }
+
+    /// Returns an iterator over the requested ranges, in request order.
+    pub fn iter(&self) -> impl Iterator<Item = &HttpRange> {
+        self.0.iter()
+    }
+
+    /// Returns the number of requested ranges.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no range was requested.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Removes the ranges that were already requested, keeping the first occurrence
+    /// of each one in request order.
+    ///
+    /// Unlike [`HttpRanges::coalesce`], only identical ranges are removed.
+    pub fn dedup(&mut self) {
+        let mut seen = HashSet::with_capacity(self.0.len());
+        self.0.retain(|range| seen.insert(*range));
+    }
+
+    /// Returns the [`ContentRange`] of the first satisfiable range, ignoring the rest.
+    ///
+    /// Honoring a single range instead of assembling a multipart response is a
+    /// legitimate server strategy, see [RFC 9110 Section 14.2].
+    ///
+    /// [RFC 9110 Section 14.2]: https://www.rfc-editor.org/rfc/rfc9110#section-14.2
+    pub fn first_satisfiable(&self, size: NonZeroU64) -> Option<ContentRange> {
+        self.0
+            .iter()
+            .find_map(|range| file_range(size.get(), Some(*range)).ok())
+    }
+
+    /// Returns how many of the ranges are satisfiable for a representation of
+    /// `size` bytes, without resolving them.
+    ///
+    /// Per [RFC 9110 Section 15.5.17], a `416 Range Not Satisfiable` is only due
+    /// when it is `0`.
+    ///
+    /// [RFC 9110 Section 15.5.17]: https://www.rfc-editor.org/rfc/rfc9110#section-15.5.17
+    pub fn count_satisfiable(&self, size: NonZeroU64) -> usize {
+        self.0
+            .iter()
+            .filter(|range| range.is_satisfiable(size))
+            .count()
+    }
+
+    /// Returns an iterator serving each range of the `body` one at a time, in
+    /// request order.
+    ///
+    /// Unlike a `multipart/byteranges` response, the framing of the parts is left
+    /// to the caller, which can for instance apply its own rate limiting between
+    /// them. Every requested range yields exactly one item, and the unsatisfiable
+    /// ones yield an [`UnsatisfiableRange`]. The ranges are neither deduplicated
+    /// nor coalesced, see [`HttpRanges::dedup`].
+    pub fn into_serving_iter(
+        self,
+        body: Bytes,
+    ) -> impl Iterator<Item = Result<BodyRange<Bytes>, UnsatisfiableRange>> {
+        let size = u64::try_from(body.len()).expect("we do not support 128bit usize");
+
+        self.0.into_iter().map(move |range| {
+            let content_range = file_range(size, Some(range))?;
+            let start =
+                usize::try_from(content_range.range().start).expect("u64 doesn't fit usize");
+            let end = usize::try_from(content_range.range().end).expect("u64 doesn't fit usize");
+
+            Ok(BodyRange::new(
+                body.slice(start..end),
+                content_range.header(),
+            ))
+        })
+    }
+
+    /// Resolves every range against a representation of `size` bytes and merges
+    /// the ones that overlap or are adjacent.
+    ///
+    /// Unsatisfiable ranges are dropped. The returned ranges are sorted by their start.
+    pub fn coalesce(&self, size: u64) -> Vec<OrderedRange> {
+        let resolved = self
+            .0
+            .iter()
+            .filter_map(|range| range.resolve(size))
+            .collect::<Vec<_>>();
+
+        coalesce(resolved)
+    }
+
+    /// Like [`HttpRanges::coalesce`], but also returns the indices, in request
+    /// order, of the requested ranges covered by each coalesced range.
+    ///
+    /// Unsatisfiable ranges are dropped, so their index never appears.
+    pub fn coalesce_with_provenance(&self, size: u64) -> Vec<(OrderedRange, Vec<usize>)> {
+        let mut resolved = self
+            .0
+            .iter()
+            .enumerate()
+            .filter_map(|(i, range)| range.resolve(size).map(|range| (range, i)))
+            .collect::<Vec<_>>();
+        resolved.sort_unstable_by_key(|(range, i)| (range.start(), *i));
+
+        let mut coalesced: Vec<(OrderedRange, Vec<usize>)> = Vec::with_capacity(resolved.len());
+        for (range, i) in resolved {
+            match coalesced.last_mut() {
+                Some((last, indices)) if range.start() <= last.end().saturating_add(1) => {
+                    last.end = last.end.max(range.end());
+                    indices.push(i);
+                }
+                _ => coalesced.push((range, vec![i])),
+            }
+        }
+        for (_, indices) in &mut coalesced {
+            indices.sort_unstable();
+        }
+
+        coalesced
+    }
+
+    fn parse(
+        s: &str,
+        mode: ParseMode,
+        max_len: usize,
+    ) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        let ranges_str = strip_unit(s, mode, max_len)?;
+        Self::parse_range_set(ranges_str, mode)
+    }
+
+    fn parse_range_set(
+        ranges_str: &str,
+        mode: ParseMode,
+    ) -> Result<Self, ParseHttpRangeOrContentRangeError> {
+        let mut ranges = Vec::new();
+        for range_str in ranges_str.split(',') {
+            match range_str.trim() {
+                "" if mode == ParseMode::Lenient => {}
+                "" => return Err(ParseHttpRangeOrContentRangeError::Malformed(HEADER_KIND)),
+                range_str => ranges.push(parse_range_spec(range_str, mode)?),
+            }
+        }
+
+        if ranges.is_empty() {
+            return Err(ParseHttpRangeOrContentRangeError::Malformed(HEADER_KIND));
+        }
+
+        Ok(Self(ranges))
+    }
+}
+
+/// Sorts `ranges` by their start and merges the ones that overlap or are adjacent.
+pub(crate) fn coalesce(mut ranges: Vec<OrderedRange>) -> Vec<OrderedRange> {
+    ranges.sort_unstable_by_key(OrderedRange::start);
+
+    let mut coalesced: Vec<OrderedRange> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match coalesced.last_mut() {
+            Some(last) if range.start() <= last.end().saturating_add(1) => {
+                last.end = last.end.max(range.end());
+            }
+            _ => coalesced.push(range),
+        }
+    }
+
+    coalesced
+}
+
+impl FromStr for HttpRanges {
+    type Err = ParseHttpRangeOrContentRangeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, ParseMode::Standard, DEFAULT_MAX_HEADER_LEN)
+    }
+}
+
+/// Builds [`HttpRanges`] from a list of ranges, rejecting an empty list with
+/// [`EmptyRanges`], since `bytes=` is not a valid `Range` header value.
+impl TryFrom<Vec<HttpRange>> for HttpRanges {
+    type Error = EmptyRanges;
+
+    fn try_from(ranges: Vec<HttpRange>) -> Result<Self, Self::Error> {
+        if ranges.is_empty() {
+            return Err(EmptyRanges);
+        }
+
+        Ok(Self(ranges))
+    }
+}
+
+/// An error that may occur when creating [`HttpRanges`] from an empty list of ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("a `Range` header requires at least one range")]
+pub struct EmptyRanges;
+
+#[cfg(feature = "http")]
+impl TryFrom<&HeaderValue> for HttpRanges {
+    type Error = ParseHttpRangeOrContentRangeError;
+    fn try_from(value: &HeaderValue) -> Result<Self, Self::Error> {
+        value
+            .to_str()
+            .map_err(|_| ParseHttpRangeOrContentRangeError::ContainsNonVisibleASCII(HEADER_KIND))?
+            .parse::<Self>()
+    }
+}
+
+#[cfg(feature = "http")]
+impl TryFrom<HeaderValue> for HttpRanges {
+    type Error = ParseHttpRangeOrContentRangeError;
+
+    fn try_from(value: HeaderValue) -> Result<Self, Self::Error> {
+        Self::try_from(&value)
+    }
+}
+
+impl Display for HttpRanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_unit(UNIT, f)
+    }
+}
+
+impl HttpRanges {
+    /// Writes these ranges with `unit` as their range unit.
+    pub(crate) fn fmt_with_unit(&self, unit: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{unit}=")?;
+        for (i, range) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            match range {
+                HttpRange::StartingPoint(start) => write!(f, "{start}-")?,
+                HttpRange::Range(range) => write!(f, "{range}")?,
+                HttpRange::Suffix(suffix) => write!(f, "-{suffix}")?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -11,11 +11,174 @@ fn unsuccessful_ordered_range() {
     assert!(OrderedRange::new(11..=10).is_err())
 }
 
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic = "`start` is greater than `end`"]
+fn unchecked_ordered_range_asserts_in_debug() {
+    OrderedRange::new_unchecked(11, 10);
+}
+
+#[test]
+fn ordered_range_from_half_open() {
+    use crate::headers::InvalidHalfOpenRange;
+
+    assert_eq!(
+        OrderedRange::from_half_open(10..20),
+        Ok(OrderedRange::new(10..=19).unwrap())
+    );
+    assert_eq!(
+        OrderedRange::from_half_open(10..11),
+        Ok(OrderedRange::new(10..=10).unwrap())
+    );
+    assert_eq!(
+        OrderedRange::from_half_open(0..u64::MAX),
+        Ok(OrderedRange::new(0..=u64::MAX - 1).unwrap())
+    );
+    assert_eq!(
+        OrderedRange::from_half_open(u64::MAX..u64::MAX),
+        Err(InvalidHalfOpenRange::Empty)
+    );
+    assert_eq!(
+        OrderedRange::from_half_open(10..10),
+        Err(InvalidHalfOpenRange::Empty)
+    );
+    assert!(matches!(
+        OrderedRange::from_half_open(11..10),
+        Err(InvalidHalfOpenRange::Unordered(_))
+    ));
+}
+
+#[test]
+fn ordered_range_clamp_to_size() {
+    use std::num::NonZeroU64;
+
+    let size = NonZeroU64::new(100).unwrap();
+    let range = |range| OrderedRange::new(range).unwrap();
+
+    assert_eq!(range(10..=20).clamp_to_size(size), Some(range(10..=20)));
+    assert_eq!(range(10..=99).clamp_to_size(size), Some(range(10..=99)));
+    assert_eq!(range(10..=500).clamp_to_size(size), Some(range(10..=99)));
+    assert_eq!(
+        range(99..=u64::MAX).clamp_to_size(size),
+        Some(range(99..=99))
+    );
+
+    assert_eq!(range(100..=500).clamp_to_size(size), None);
+    assert_eq!(range(200..=200).clamp_to_size(size), None);
+}
+
+#[test]
+fn bytes_unit_matches_the_headers() {
+    use crate::{
+        BYTES_UNIT,
+        headers::{range::HttpRange, range_unit::RangeUnit},
+    };
+
+    assert_eq!(BYTES_UNIT, "bytes");
+    assert_eq!(RangeUnit::Bytes.as_str(), BYTES_UNIT);
+    assert!(
+        OrderedRange::new(0..=9)
+            .map(HttpRange::Range)
+            .unwrap()
+            .to_string()
+            .starts_with(BYTES_UNIT)
+    );
+}
+
+#[test]
+fn ordered_range_is_aligned() {
+    use std::num::NonZeroU64;
+
+    let block = NonZeroU64::new(4096).unwrap();
+    let range = |range| OrderedRange::new(range).unwrap();
+
+    assert!(range(0..=10).is_aligned(block));
+    assert!(range(8192..=9000).is_aligned(block));
+    assert!(!range(5000..=6000).is_aligned(block));
+    assert!(range(5000..=6000).is_aligned(NonZeroU64::MIN));
+}
+
+#[test]
+fn ordered_range_into_tuple() {
+    assert_eq!(OrderedRange::new(10..=20).unwrap().into_tuple(), (10, 20));
+    assert_eq!(
+        OrderedRange::new(0..=u64::MAX).unwrap().into_tuple(),
+        (0, u64::MAX)
+    );
+}
+
+#[test]
+#[cfg(feature = "http")]
+fn header_types_try_from_owned_value() {
+    use http::HeaderValue;
+
+    use crate::headers::{
+        content_range::{Bound, HttpContentRange},
+        etag::ETag,
+        if_range::IfRange,
+        range::HttpRange,
+        ranges::HttpRanges,
+    };
+
+    let range = HttpRange::try_from(HeaderValue::from_static("bytes=10-")).unwrap();
+    assert_eq!(range, HttpRange::StartingPoint(10));
+
+    let ranges = HttpRanges::try_from(HeaderValue::from_static("bytes=10-, -5")).unwrap();
+    assert_eq!(ranges.len(), 2);
+
+    let content_range =
+        HttpContentRange::try_from(HeaderValue::from_static("bytes 10-20/50")).unwrap();
+    assert_eq!(
+        content_range,
+        HttpContentRange::Bound(Bound::new(10..=20, Some(50)).unwrap())
+    );
+
+    let if_range = IfRange::try_from(HeaderValue::from_static("\"abc\"")).unwrap();
+    assert!(matches!(if_range, IfRange::ETag(_)));
+
+    let etag = ETag::try_from(HeaderValue::from_static("W/\"abc\"")).unwrap();
+    assert!(etag.is_weak());
+}
+
+#[test]
+fn range_types_are_hashable() {
+    use std::collections::HashSet;
+
+    use crate::headers::{
+        content_range::{Bound, HttpContentRange, Unsatisfiable},
+        range::HttpRange,
+    };
+
+    let ranges = HashSet::from([
+        HttpRange::StartingPoint(10),
+        HttpRange::Suffix(10),
+        HttpRange::Range(OrderedRange::new(10..=20).unwrap()),
+        HttpRange::StartingPoint(10),
+    ]);
+    assert_eq!(ranges.len(), 3);
+
+    let content_ranges = HashSet::from([
+        HttpContentRange::Bound(Bound::new(10..=20, Some(50)).unwrap()),
+        HttpContentRange::Bound(Bound::new(10..=20, None).unwrap()),
+        HttpContentRange::Unsatisfiable(Unsatisfiable::new(50)),
+        HttpContentRange::Unsatisfiable(Unsatisfiable::new(50)),
+    ]);
+    assert_eq!(content_ranges.len(), 3);
+}
+
 #[cfg(test)]
 mod content_range {
-    use crate::headers::{
-        InvalidOrderedRange, OrderedRange,
-        content_range::{Bound, HttpContentRange, InvalidBound, Unsatisfiable},
+    use std::num::NonZeroU64;
+
+    #[cfg(feature = "http")]
+    use crate::headers::{HeaderKind, ParseHttpRangeOrContentRangeError};
+    use crate::{
+        file_range,
+        headers::{
+            InvalidOrderedRange, OrderedRange,
+            content_range::{Bound, HttpContentRange, InvalidBound, Unsatisfiable},
+            range::HttpRange,
+        },
     };
 
     #[test]
@@ -55,6 +218,27 @@ mod content_range {
         );
     }
 
+    #[test]
+    fn bound_start_beyond_size() {
+        let err = Bound::new(100..=150, Some(50)).unwrap_err();
+        assert_eq!(
+            err,
+            InvalidBound::StartBeyondSize {
+                range: OrderedRange::new(100..=150).unwrap(),
+                size: 50
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "The provided range `start`: 100 is greater than or equal to `size`: 50"
+        );
+        assert_eq!(err.overflow(), Some(101));
+        assert!(matches!(
+            Bound::new(50..=50, Some(50)),
+            Err(InvalidBound::StartBeyondSize { .. })
+        ));
+    }
+
     #[test]
     fn successful_sized_bound_parsing() {
         assert_eq!(
@@ -87,12 +271,265 @@ mod content_range {
         );
     }
 
+    #[test]
+    fn range_inclusive() {
+        let bound = Bound::new(10..=20, Some(50)).unwrap();
+        assert_eq!(bound.range_inclusive(), 10..=20);
+        assert_eq!(Bound::new(0..=0, None).unwrap().range_inclusive(), 0..=0);
+    }
+
+    #[test]
+    fn size_known() {
+        assert!(Bound::new(10..=20, Some(50)).unwrap().is_size_known());
+        assert!(!Bound::new(10..=20, None).unwrap().is_size_known());
+    }
+
+    #[test]
+    fn invalid_bound_overflow() {
+        assert_eq!(
+            Bound::new(10..=50, Some(20)).unwrap_err().overflow(),
+            Some(31)
+        );
+        assert_eq!(
+            Bound::new(0..=20, Some(20)).unwrap_err().overflow(),
+            Some(1)
+        );
+        assert_eq!(
+            Bound::new(0..=u64::MAX, Some(0)).unwrap_err().overflow(),
+            Some(u64::MAX)
+        );
+        assert_eq!(Bound::new(11..=10, None).unwrap_err().overflow(), None);
+        assert_eq!(
+            Bound::from_exclusive(10..10, None).unwrap_err().overflow(),
+            None
+        );
+    }
+
+    #[test]
+    fn hand_built_invalid_bound_overflow() {
+        let range = OrderedRange::new(0..=5).unwrap();
+        assert_eq!(
+            InvalidBound::InvalidSize { range, size: 10 }.overflow(),
+            None
+        );
+        assert_eq!(
+            InvalidBound::StartBeyondSize { range, size: 10 }.overflow(),
+            None
+        );
+        assert_eq!(
+            InvalidBound::InvalidSize { range, size: 5 }.overflow(),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn bound_from_exclusive() {
+        assert_eq!(
+            Bound::from_exclusive(10..21, Some(50)),
+            Bound::new(10..=20, Some(50))
+        );
+        assert_eq!(Bound::from_exclusive(0..1, None), Bound::new(0..=0, None));
+        assert_eq!(
+            Bound::from_exclusive(10..10, Some(50)),
+            Err(InvalidBound::EmptyRange)
+        );
+        assert_eq!(
+            Bound::from_exclusive(0..0, None),
+            Err(InvalidBound::EmptyRange)
+        );
+        assert_eq!(
+            Bound::from_exclusive(20..10, None),
+            Err(InvalidBound::EmptyRange)
+        );
+        assert_eq!(
+            Bound::from_exclusive(0..51, Some(50)),
+            Err(InvalidBound::InvalidSize {
+                range: OrderedRange::new(0..=50).unwrap(),
+                size: 50
+            })
+        );
+    }
+
+    #[test]
+    fn bound_constructor() {
+        assert_eq!(
+            HttpContentRange::bound(10..=20, Some(50)),
+            Ok(HttpContentRange::Bound(
+                Bound::new(10..=20, Some(50)).unwrap()
+            ))
+        );
+        assert_eq!(
+            HttpContentRange::bound(10..=50, Some(20)),
+            Err(InvalidBound::InvalidSize {
+                range: OrderedRange::new(10..=50).unwrap(),
+                size: 20
+            })
+        );
+    }
+
+    #[test]
+    fn same_range_ignores_size() {
+        let unsized_ = Bound::new(0..=99, None).unwrap();
+        let sized = Bound::new(0..=99, Some(200)).unwrap();
+        assert_ne!(unsized_, sized);
+        assert!(unsized_.same_range(&sized));
+        assert!(sized.same_range(&Bound::new(0..=99, Some(300)).unwrap()));
+        assert!(!sized.same_range(&Bound::new(0..=98, Some(200)).unwrap()));
+    }
+
+    #[test]
+    fn unsized_bound_round_trip() {
+        for (start, end) in [
+            (0, 0),
+            (0, u64::MAX),
+            (u64::MAX, u64::MAX),
+            (1, u64::MAX - 1),
+        ] {
+            let content_range = HttpContentRange::Bound(Bound::new(start..=end, None).unwrap());
+            let parsed = content_range
+                .to_string()
+                .parse::<HttpContentRange>()
+                .unwrap();
+            assert_eq!(parsed, content_range);
+
+            let HttpContentRange::Bound(bound) = parsed else {
+                unreachable!()
+            };
+            assert!(!bound.is_size_known());
+        }
+    }
+
     #[test]
     fn unit_is_case_insensitive() {
         assert_eq!(
             "BYTES 10-20/50".parse::<HttpContentRange>().unwrap(),
             HttpContentRange::Bound(Bound::new(10..=20, Some(50)).unwrap())
         );
+
+        let content_range = "Bytes 0-99/200".parse::<HttpContentRange>().unwrap();
+        assert_eq!(
+            content_range,
+            HttpContentRange::Bound(Bound::new(0..=99, Some(200)).unwrap())
+        );
+        assert_eq!(content_range.to_string(), "bytes 0-99/200");
+        assert_eq!(
+            "Bytes */200".parse::<HttpContentRange>().unwrap(),
+            HttpContentRange::Unsatisfiable(Unsatisfiable::new(200))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn from_header_map() {
+        let mut headers = http::HeaderMap::new();
+        assert_eq!(HttpContentRange::from_header_map(&headers), Ok(None));
+
+        headers.insert(
+            http::header::CONTENT_RANGE,
+            "bytes 10-20/50".parse().unwrap(),
+        );
+        assert_eq!(
+            HttpContentRange::from_header_map(&headers),
+            Ok(Some(HttpContentRange::Bound(
+                Bound::new(10..=20, Some(50)).unwrap()
+            )))
+        );
+
+        headers.insert(http::header::CONTENT_RANGE, "bytes 10-20".parse().unwrap());
+        assert_eq!(
+            HttpContentRange::from_header_map(&headers),
+            Err(ParseHttpRangeOrContentRangeError::MissingSizeDelimiter(
+                HeaderKind::ContentRange
+            ))
+        );
+    }
+
+    #[test]
+    fn constructors_and_accessors() {
+        let bound = HttpContentRange::bound(0..=9, Some(10)).unwrap();
+        assert_eq!(bound.as_bound(), Some(Bound::new(0..=9, Some(10)).unwrap()));
+        assert_eq!(bound.as_unsatisfiable(), None);
+
+        let unsatisfiable = HttpContentRange::unsatisfiable(10);
+        assert_eq!(
+            unsatisfiable,
+            HttpContentRange::Unsatisfiable(Unsatisfiable::new(10))
+        );
+        assert_eq!(unsatisfiable.as_bound(), None);
+        assert_eq!(
+            unsatisfiable.as_unsatisfiable(),
+            Some(Unsatisfiable::new(10))
+        );
+    }
+
+    #[test]
+    fn resolved_slice() {
+        let content_range = HttpContentRange::bound(10..=19, Some(50)).unwrap();
+        assert_eq!(content_range.resolved_slice(), Some(10..=19));
+        assert_eq!(HttpContentRange::unsatisfiable(50).resolved_slice(), None);
+
+        let beyond_u32 = u64::from(u32::MAX) + 1;
+        let content_range = HttpContentRange::bound(0..=beyond_u32, None).unwrap();
+        assert_eq!(
+            content_range.resolved_slice(),
+            usize::try_from(beyond_u32).ok().map(|end| 0..=end)
+        );
+        #[cfg(target_pointer_width = "32")]
+        assert_eq!(content_range.resolved_slice(), None);
+    }
+
+    #[test]
+    fn empty_resource() {
+        let content_range = HttpContentRange::empty_resource();
+        assert_eq!(
+            content_range,
+            HttpContentRange::Unsatisfiable(Unsatisfiable::new(0))
+        );
+        assert_eq!(content_range.to_string(), "bytes */0");
+        assert_eq!(
+            file_range(0, Some(HttpRange::StartingPoint(0)))
+                .unwrap_err()
+                .header(),
+            content_range
+        );
+    }
+
+    #[test]
+    fn write_offset() {
+        let content_range: HttpContentRange = "bytes 100-199/500".parse().unwrap();
+        assert_eq!(content_range.write_offset(), Some(100));
+
+        let content_range: HttpContentRange = "bytes 0-9/*".parse().unwrap();
+        assert_eq!(content_range.write_offset(), Some(0));
+
+        let content_range: HttpContentRange = "bytes */500".parse().unwrap();
+        assert_eq!(content_range.write_offset(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn from_header_map_rejects_multiple_headers() {
+        let mut headers = http::HeaderMap::new();
+        headers.append(http::header::CONTENT_RANGE, "bytes 0-9/50".parse().unwrap());
+        headers.append(
+            http::header::CONTENT_RANGE,
+            "bytes 10-19/50".parse().unwrap(),
+        );
+        assert_eq!(
+            HttpContentRange::from_header_map(&headers),
+            Err(ParseHttpRangeOrContentRangeError::MultipleHeaders(
+                HeaderKind::ContentRange
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn header_pair() {
+        let content_range = HttpContentRange::Bound(Bound::new(10..=20, Some(50)).unwrap());
+        let mut headers = http::HeaderMap::new();
+        headers.extend([content_range.as_header_pair()]);
+        assert_eq!(headers[http::header::CONTENT_RANGE], "bytes 10-20/50");
     }
 
     #[test]
@@ -111,6 +548,74 @@ mod content_range {
         );
     }
 
+    #[test]
+    fn complete_response() {
+        let sized = HttpContentRange::Bound(Bound::new(0..=49, Some(50)).unwrap());
+        assert!(sized.is_complete_response(50));
+
+        let unsized_ = HttpContentRange::Bound(Bound::new(0..=49, None).unwrap());
+        assert!(unsized_.is_complete_response(50));
+    }
+
+    #[test]
+    fn incomplete_response() {
+        let partial = HttpContentRange::Bound(Bound::new(10..=49, Some(50)).unwrap());
+        assert!(!partial.is_complete_response(50));
+
+        let short = HttpContentRange::Bound(Bound::new(0..=19, None).unwrap());
+        assert!(!short.is_complete_response(50));
+
+        let size_mismatch = HttpContentRange::Bound(Bound::new(0..=49, Some(100)).unwrap());
+        assert!(!size_mismatch.is_complete_response(50));
+
+        let unsatisfiable = HttpContentRange::Unsatisfiable(Unsatisfiable::new(50));
+        assert!(!unsatisfiable.is_complete_response(50));
+    }
+
+    #[test]
+    fn full_from_start() {
+        let full = HttpContentRange::bound(0..=49, Some(50)).unwrap();
+        assert!(full.is_full_from_start());
+        assert!(full.matches_requested_range(HttpRange::StartingPoint(0)));
+
+        for partial in [
+            HttpContentRange::bound(0..=48, Some(50)).unwrap(),
+            HttpContentRange::bound(1..=49, Some(50)).unwrap(),
+            HttpContentRange::bound(0..=49, None).unwrap(),
+            HttpContentRange::unsatisfiable(50),
+            HttpContentRange::empty_resource(),
+        ] {
+            assert!(!partial.is_full_from_start(), "{partial:?}");
+        }
+    }
+
+    #[test]
+    fn from_suffix() {
+        let size = NonZeroU64::new(200).unwrap();
+        assert_eq!(
+            HttpContentRange::from_suffix(50, size).unwrap(),
+            HttpContentRange::Bound(Bound::new(150..=199, Some(200)).unwrap())
+        );
+    }
+
+    #[test]
+    fn from_suffix_clamped() {
+        let size = NonZeroU64::new(200).unwrap();
+        assert_eq!(
+            HttpContentRange::from_suffix(500, size).unwrap(),
+            HttpContentRange::Bound(Bound::new(0..=199, Some(200)).unwrap())
+        );
+    }
+
+    #[test]
+    fn from_suffix_zero_is_unsatisfiable() {
+        let size = NonZeroU64::new(200).unwrap();
+        assert_eq!(
+            HttpContentRange::from_suffix(0, size).unwrap_err().header(),
+            HttpContentRange::Unsatisfiable(Unsatisfiable::new(200))
+        );
+    }
+
     mod expected_range {
         use crate::headers::{
             OrderedRange,
@@ -126,6 +631,47 @@ mod content_range {
             assert!(content_range.matches_requested_range(range));
         }
 
+        #[test]
+        fn strict_detects_truncated_starting_point() {
+            let range = HttpRange::StartingPoint(50);
+            let truncated = HttpContentRange::Bound(Bound::new(50..=60, Some(200)).unwrap());
+            let complete = HttpContentRange::Bound(Bound::new(50..=199, Some(200)).unwrap());
+
+            assert!(truncated.matches_requested_range(range));
+            assert!(!truncated.matches_requested_range_strict(range, 200));
+            assert!(complete.matches_requested_range_strict(range, 200));
+        }
+
+        #[test]
+        fn strict_checks_size_and_clamping() {
+            let range = HttpRange::Range(OrderedRange::new(0..=499).unwrap());
+            let clamped = HttpContentRange::Bound(Bound::new(0..=199, Some(200)).unwrap());
+            let unsized_ = HttpContentRange::Bound(Bound::new(0..=199, None).unwrap());
+
+            assert!(clamped.matches_requested_range_strict(range, 200));
+            assert!(unsized_.matches_requested_range_strict(range, 200));
+            assert!(!clamped.matches_requested_range_strict(range, 300));
+
+            let suffix = HttpContentRange::Bound(Bound::new(150..=199, Some(200)).unwrap());
+            assert!(suffix.matches_requested_range_strict(HttpRange::Suffix(50), 200));
+            assert!(!suffix.matches_requested_range_strict(HttpRange::Suffix(40), 200));
+        }
+
+        #[test]
+        fn strict_unsatisfiable() {
+            let content_range = HttpContentRange::Unsatisfiable(Unsatisfiable::new(200));
+
+            assert!(
+                content_range.matches_requested_range_strict(HttpRange::StartingPoint(200), 200)
+            );
+            assert!(
+                !content_range.matches_requested_range_strict(HttpRange::StartingPoint(199), 200)
+            );
+            assert!(
+                !content_range.matches_requested_range_strict(HttpRange::StartingPoint(300), 300)
+            );
+        }
+
         #[test]
         fn satisfiable_range_does_not_match_unsatisfiable() {
             let range = HttpRange::Range(OrderedRange::new(10..=50).unwrap());
@@ -134,6 +680,34 @@ mod content_range {
             assert!(!content_range.matches_requested_range(range));
         }
 
+        #[test]
+        fn satisfiable_starting_point_does_not_match_unsatisfiable() {
+            let content_range = HttpContentRange::Unsatisfiable(Unsatisfiable::new(200));
+
+            assert!(!content_range.matches_requested_range(HttpRange::StartingPoint(0)));
+            assert!(!content_range.matches_requested_range(HttpRange::StartingPoint(10)));
+            assert!(!content_range.matches_requested_range(HttpRange::StartingPoint(199)));
+            assert!(content_range.matches_requested_range(HttpRange::StartingPoint(200)));
+        }
+
+        #[test]
+        fn satisfiable_range_at_boundary_does_not_match_unsatisfiable() {
+            let content_range = HttpContentRange::Unsatisfiable(Unsatisfiable::new(200));
+
+            let last_byte = HttpRange::Range(OrderedRange::new(199..=300).unwrap());
+            assert!(!content_range.matches_requested_range(last_byte));
+            let past_the_end = HttpRange::Range(OrderedRange::new(200..=300).unwrap());
+            assert!(content_range.matches_requested_range(past_the_end));
+        }
+
+        #[test]
+        fn nonzero_suffix_on_empty_does_not_match_unsatisfiable() {
+            let content_range = HttpContentRange::Unsatisfiable(Unsatisfiable::new(0));
+
+            assert!(!content_range.matches_requested_range(HttpRange::Suffix(5)));
+            assert!(content_range.matches_requested_range(HttpRange::StartingPoint(0)));
+        }
+
         #[test]
         fn range_start_beyond_size_matches_unsatisfiable() {
             let range = HttpRange::Range(OrderedRange::new(20..=50).unwrap());
@@ -198,6 +772,30 @@ mod content_range {
             assert!(content_range.matches_requested_range(range));
         }
 
+        #[test]
+        fn suffix_exactly_clamped_to_file_size_matches() {
+            let range = HttpRange::Suffix(100);
+            let content_range = HttpContentRange::Bound(Bound::new(0..=99, Some(100)).unwrap());
+
+            assert!(content_range.matches_requested_range(range));
+        }
+
+        #[test]
+        fn suffix_clamped_with_unknown_size_matches() {
+            let range = HttpRange::Suffix(100);
+            let content_range = HttpContentRange::Bound(Bound::new(0..=49, None).unwrap());
+
+            assert!(content_range.matches_requested_range(range));
+        }
+
+        #[test]
+        fn suffix_shorter_than_requested_not_from_start_does_not_match() {
+            let range = HttpRange::Suffix(10);
+            let content_range = HttpContentRange::Bound(Bound::new(15..=19, Some(20)).unwrap());
+
+            assert!(!content_range.matches_requested_range(range));
+        }
+
         #[test]
         fn suffix_not_at_boundary_does_not_match() {
             let range = HttpRange::Suffix(5);
@@ -234,7 +832,13 @@ mod content_range {
 
 #[cfg(test)]
 mod range {
-    use crate::headers::{OrderedRange, ParseHttpRangeOrContentRangeError, range::HttpRange};
+    use std::num::{NonZeroU64, NonZeroUsize};
+
+    use crate::headers::{
+        HeaderKind, OrderedRange, ParseHttpRangeOrContentRangeError,
+        range::{HttpRange, InvalidFraction},
+        ranges::HttpRanges,
+    };
 
     #[test]
     fn successful_starting_parsing() {
@@ -245,44 +849,116 @@ mod range {
     }
 
     #[test]
-    fn successful_starting_to_string() {
-        assert_eq!("bytes=50-", &HttpRange::StartingPoint(50).to_string());
+    fn byte_indices_match_content_length() {
+        let size = 10;
+        for range in [
+            HttpRange::StartingPoint(4),
+            HttpRange::Range(OrderedRange::new(2..=5).unwrap()),
+            HttpRange::Range(OrderedRange::new(2..=50).unwrap()),
+            HttpRange::Suffix(3),
+            HttpRange::Suffix(30),
+        ] {
+            let content_range = crate::file_range(size, Some(range)).unwrap();
+            let indices = range
+                .byte_indices(NonZeroU64::new(size).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                indices,
+                content_range.range().clone().collect::<Vec<_>>(),
+                "{range}"
+            );
+        }
     }
 
     #[test]
-    fn successful_range_parsing() {
-        assert_eq!(
-            "bytes=50-100".parse::<HttpRange>().unwrap(),
-            HttpRange::Range(OrderedRange::new(50..=100).unwrap())
-        );
+    fn byte_indices_empty_for_unsatisfiable() {
+        let size = NonZeroU64::new(10).unwrap();
+        assert_eq!(HttpRange::StartingPoint(10).byte_indices(size).count(), 0);
+        assert_eq!(HttpRange::Suffix(0).byte_indices(size).count(), 0);
     }
 
     #[test]
-    fn successful_range_to_string() {
+    #[cfg(feature = "http")]
+    fn from_header_map() {
+        let mut headers = http::HeaderMap::new();
+        assert_eq!(HttpRange::from_header_map(&headers), Ok(None));
+
+        headers.insert(http::header::RANGE, "bytes=50-".parse().unwrap());
         assert_eq!(
-            "bytes=50-100",
-            &HttpRange::Range(OrderedRange::new(50..=100).unwrap()).to_string()
+            HttpRange::from_header_map(&headers),
+            Ok(Some(HttpRange::StartingPoint(50)))
+        );
+
+        headers.insert(http::header::RANGE, "bytes=50".parse().unwrap());
+        assert_eq!(
+            HttpRange::from_header_map(&headers),
+            Err(ParseHttpRangeOrContentRangeError::MalformedRange(
+                HeaderKind::Range
+            ))
         );
     }
 
     #[test]
-    fn successful_suffix_parsing() {
+    #[cfg(feature = "http")]
+    fn from_header_map_rejects_multiple_headers() {
+        let mut headers = http::HeaderMap::new();
+        headers.append(http::header::RANGE, "bytes=0-9".parse().unwrap());
+        headers.append(http::header::RANGE, "bytes=10-19".parse().unwrap());
         assert_eq!(
-            "bytes=-100".parse::<HttpRange>().unwrap(),
-            HttpRange::Suffix(100)
+            HttpRange::from_header_map(&headers),
+            Err(ParseHttpRangeOrContentRangeError::MultipleHeaders(
+                HeaderKind::Range
+            ))
         );
     }
 
     #[test]
-    fn successful_suffix_to_string() {
-        assert_eq!("bytes=-100", &HttpRange::Suffix(100).to_string());
+    #[cfg(feature = "http")]
+    fn header_pair() {
+        let mut headers = http::HeaderMap::new();
+        headers.extend([HttpRange::StartingPoint(50).as_header_pair()]);
+        assert_eq!(headers[http::header::RANGE], "bytes=50-");
     }
 
     #[test]
-    fn empty_input() {
-        assert_eq!(
-            "".parse::<HttpRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::Empty
+    fn successful_starting_to_string() {
+        assert_eq!("bytes=50-", &HttpRange::StartingPoint(50).to_string());
+    }
+
+    #[test]
+    fn successful_range_parsing() {
+        assert_eq!(
+            "bytes=50-100".parse::<HttpRange>().unwrap(),
+            HttpRange::Range(OrderedRange::new(50..=100).unwrap())
+        );
+    }
+
+    #[test]
+    fn successful_range_to_string() {
+        assert_eq!(
+            "bytes=50-100",
+            &HttpRange::Range(OrderedRange::new(50..=100).unwrap()).to_string()
+        );
+    }
+
+    #[test]
+    fn successful_suffix_parsing() {
+        assert_eq!(
+            "bytes=-100".parse::<HttpRange>().unwrap(),
+            HttpRange::Suffix(100)
+        );
+    }
+
+    #[test]
+    fn successful_suffix_to_string() {
+        assert_eq!("bytes=-100", &HttpRange::Suffix(100).to_string());
+    }
+
+    #[test]
+    fn empty_input() {
+        assert_eq!(
+            "".parse::<HttpRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Empty(HeaderKind::Range)
         );
     }
 
@@ -290,15 +966,42 @@ mod range {
     fn whitespace_only_input() {
         assert_eq!(
             "   ".parse::<HttpRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::Empty
+            ParseHttpRangeOrContentRangeError::Empty(HeaderKind::Range)
         );
     }
 
+    #[test]
+    fn error_names_the_header() {
+        let err = "bytes50-100".parse::<HttpRange>().unwrap_err();
+        assert_eq!(err.header_kind(), HeaderKind::Range);
+        assert_eq!(err.to_string(), "malformed Range header value");
+    }
+
     #[test]
     fn missing_equals() {
         assert_eq!(
             "bytes50-100".parse::<HttpRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::Malformed
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+        );
+    }
+
+    #[test]
+    fn content_range_syntax_rejected() {
+        assert_eq!(
+            "bytes=0-99/200".parse::<HttpRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+        );
+    }
+
+    #[test]
+    fn multiple_equals_rejected() {
+        assert_eq!(
+            "bytes=0=99".parse::<HttpRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+        );
+        assert_eq!(
+            "bytes==0-99".parse::<HttpRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
         );
     }
 
@@ -306,7 +1009,7 @@ mod range {
     fn wrong_unit() {
         assert_eq!(
             "items=0-10".parse::<HttpRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::InvalidUnit
+            ParseHttpRangeOrContentRangeError::InvalidUnit(HeaderKind::Range)
         );
     }
 
@@ -322,11 +1025,158 @@ mod range {
         );
     }
 
+    #[test]
+    fn missing_unit_rejected() {
+        assert_eq!(
+            "=0-99".parse::<HttpRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+        );
+    }
+
+    #[test]
+    fn lenient_missing_unit() {
+        assert_eq!(
+            HttpRange::parse_lenient("=0-99").unwrap(),
+            HttpRange::Range(OrderedRange::new(0..=99).unwrap())
+        );
+        assert_eq!(
+            HttpRange::parse_lenient("=-50").unwrap(),
+            HttpRange::Suffix(50)
+        );
+        assert_eq!(
+            HttpRange::parse_lenient("0-").unwrap(),
+            HttpRange::StartingPoint(0)
+        );
+    }
+
+    #[test]
+    fn constructors_and_predicates() {
+        let range = HttpRange::range(0..=9).unwrap();
+        assert_eq!(range, HttpRange::Range(OrderedRange::new(0..=9).unwrap()));
+        assert!(range.is_range());
+        assert!(!range.is_starting_point() && !range.is_suffix());
+        assert!(HttpRange::range(10..=9).is_err());
+
+        assert!(HttpRange::starting_point(5).is_starting_point());
+        assert!(HttpRange::suffix(5).is_suffix());
+    }
+
+    #[test]
+    fn lenient_discards_parameters() {
+        assert_eq!(
+            HttpRange::parse_lenient("bytes=0-99;q=0.5").unwrap(),
+            HttpRange::Range(OrderedRange::new(0..=99).unwrap())
+        );
+        assert_eq!(
+            HttpRange::parse_lenient("bytes=-50 ; foo=bar; baz").unwrap(),
+            HttpRange::Suffix(50)
+        );
+        assert_eq!(
+            HttpRange::parse_lenient(";q=0.5").unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Empty(HeaderKind::Range)
+        );
+
+        assert_eq!(
+            HttpRange::parse_strict("bytes=0-99;q=0.5").unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+        );
+        assert!("bytes=0-99;q=0.5".parse::<HttpRange>().is_err());
+    }
+
+    #[test]
+    fn lenient_still_rejects_wrong_unit() {
+        assert_eq!(
+            HttpRange::parse_lenient("items=0-10").unwrap_err(),
+            ParseHttpRangeOrContentRangeError::InvalidUnit(HeaderKind::Range)
+        );
+    }
+
+    #[test]
+    fn leading_zeros_accepted() {
+        assert_eq!(
+            "bytes=007-10".parse::<HttpRange>().unwrap(),
+            HttpRange::Range(OrderedRange::new(7..=10).unwrap())
+        );
+    }
+
+    #[test]
+    fn digit_separators_rejected_clearly() {
+        let err = "bytes=1_000-2_000".parse::<HttpRange>().unwrap_err();
+        assert_eq!(
+            err,
+            ParseHttpRangeOrContentRangeError::DigitSeparator(HeaderKind::Range)
+        );
+        assert!(err.to_string().contains("digit separators"));
+
+        for value in ["bytes=1_000-", "bytes=-1_0", "bytes=0-9, 10-2_000"] {
+            assert_eq!(
+                value.parse::<HttpRanges>().unwrap_err(),
+                ParseHttpRangeOrContentRangeError::DigitSeparator(HeaderKind::Range),
+                "{value:?}"
+            );
+        }
+        for value in ["bytes=_1-2", "bytes=1_-2", "bytes=1_a-2"] {
+            assert!(matches!(
+                value.parse::<HttpRange>().unwrap_err(),
+                ParseHttpRangeOrContentRangeError::InvalidRangePiece(HeaderKind::Range, _)
+            ));
+        }
+    }
+
+    #[test]
+    fn strict_rejects_overflowing_length() {
+        let max = format!("bytes=0-{}", u64::MAX);
+        assert_eq!(
+            HttpRange::parse_strict(&max).unwrap_err(),
+            ParseHttpRangeOrContentRangeError::LengthOverflow(HeaderKind::Range)
+        );
+        assert_eq!(
+            HttpRanges::parse_strict(&format!("bytes=0-9, 0-{}", u64::MAX)).unwrap_err(),
+            ParseHttpRangeOrContentRangeError::LengthOverflow(HeaderKind::Range)
+        );
+        assert_eq!(
+            max.parse::<HttpRange>().unwrap(),
+            HttpRange::Range(OrderedRange::new(0..=u64::MAX).unwrap())
+        );
+
+        assert_eq!(
+            HttpRange::parse_strict(&format!("bytes=1-{}", u64::MAX)).unwrap(),
+            HttpRange::Range(OrderedRange::new(1..=u64::MAX).unwrap())
+        );
+        assert_eq!(
+            HttpRange::parse_strict(&format!("bytes=0-{}", u64::MAX - 1)).unwrap(),
+            HttpRange::Range(OrderedRange::new(0..=u64::MAX - 1).unwrap())
+        );
+    }
+
+    #[test]
+    fn strict_rejects_leading_zeros() {
+        for range in ["bytes=007-10", "bytes=7-010", "bytes=007-", "bytes=-007"] {
+            assert_eq!(
+                HttpRange::parse_strict(range).unwrap_err(),
+                ParseHttpRangeOrContentRangeError::MalformedRange(HeaderKind::Range),
+                "{range}"
+            );
+        }
+    }
+
+    #[test]
+    fn strict_accepts_single_zero() {
+        assert_eq!(
+            HttpRange::parse_strict("bytes=0-10").unwrap(),
+            HttpRange::Range(OrderedRange::new(0..=10).unwrap())
+        );
+        assert_eq!(
+            HttpRange::parse_strict("bytes=-0").unwrap(),
+            HttpRange::Suffix(0)
+        );
+    }
+
     #[test]
     fn both_empty() {
         assert_eq!(
             "bytes=-".parse::<HttpRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::Malformed
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
         );
     }
 
@@ -335,6 +1185,43 @@ mod range {
         assert!("bytes=100-50".parse::<HttpRange>().is_err());
     }
 
+    #[test]
+    fn from_bytes() {
+        assert_eq!(
+            HttpRange::from_bytes(b"bytes=0-99").unwrap(),
+            HttpRange::Range(OrderedRange::new(0..=99).unwrap())
+        );
+        for bytes in [
+            &b"bytes=0-\xff\xfe"[..],
+            b"bytes=\x000-99",
+            "bytes=0-9é".as_bytes(),
+        ] {
+            assert_eq!(
+                HttpRange::from_bytes(bytes).unwrap_err(),
+                ParseHttpRangeOrContentRangeError::ContainsNonVisibleASCII(HeaderKind::Range)
+            );
+        }
+    }
+
+    #[test]
+    fn extra_dash_rejected() {
+        for value in ["bytes=-5-10", "bytes=5--10", "bytes=--5", "bytes=5-10-"] {
+            assert_eq!(
+                value.parse::<HttpRange>().unwrap_err(),
+                ParseHttpRangeOrContentRangeError::ExtraDash(HeaderKind::Range),
+                "{value:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn multiple_ranges_are_not_an_extra_dash() {
+        assert!(matches!(
+            "bytes=0-9,10-19".parse::<HttpRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::InvalidRangePiece(HeaderKind::Range, _)
+        ));
+    }
+
     #[test]
     fn plus_prefix_rejected_in_starting_point() {
         assert!("bytes=+50-".parse::<HttpRange>().is_err());
@@ -379,25 +1266,221 @@ mod range {
             HttpRange::Range(OrderedRange::new(0..=0).unwrap())
         );
     }
+
+    #[test]
+    fn const_constructors() {
+        const RANGES: [HttpRange; 3] = [
+            HttpRange::starting_point(10),
+            HttpRange::Range(OrderedRange::new_unchecked(0, 9)),
+            HttpRange::suffix(5),
+        ];
+        assert_eq!(
+            RANGES,
+            [
+                HttpRange::StartingPoint(10),
+                HttpRange::Range(OrderedRange::new(0..=9).unwrap()),
+                HttpRange::Suffix(5),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_covers_the_whole_representation() {
+        for (size, parts) in [(100, 1), (100, 3), (100, 4), (7, 7), (3, 10), (u64::MAX, 5)] {
+            let ranges = HttpRange::split(
+                NonZeroU64::new(size).unwrap(),
+                NonZeroUsize::new(parts).unwrap(),
+            );
+            assert_eq!(ranges.len(), parts.min(size as usize));
+
+            let mut next_start = 0;
+            for range in &ranges {
+                let HttpRange::Range(range) = range else {
+                    panic!("expected an int-range, got {range:?}");
+                };
+                assert_eq!(range.start(), next_start);
+                next_start = range.end() + 1;
+            }
+            assert_eq!(next_start, size);
+        }
+    }
+
+    #[test]
+    fn split_last_part_absorbs_remainder() {
+        let ranges = HttpRange::split(NonZeroU64::new(10).unwrap(), NonZeroUsize::new(3).unwrap());
+        assert_eq!(
+            ranges,
+            [
+                HttpRange::Range(OrderedRange::new(0..=2).unwrap()),
+                HttpRange::Range(OrderedRange::new(3..=5).unwrap()),
+                HttpRange::Range(OrderedRange::new(6..=9).unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn normalize_full_file() {
+        let size = NonZeroU64::new(100).unwrap();
+        let full = OrderedRange::new(0..=99).unwrap();
+        for range in [
+            HttpRange::StartingPoint(0),
+            HttpRange::Range(OrderedRange::new(0..=99).unwrap()),
+            HttpRange::Range(OrderedRange::new(0..=500).unwrap()),
+            HttpRange::Suffix(100),
+            HttpRange::Suffix(500),
+        ] {
+            assert_eq!(range.normalize(size), Some(full), "{range:?}");
+        }
+
+        assert_eq!(HttpRange::StartingPoint(100).normalize(size), None);
+        assert_eq!(HttpRange::Suffix(0).normalize(size), None);
+    }
+
+    #[test]
+    fn normalize_suffix() {
+        let size = NonZeroU64::new(100).unwrap();
+        assert_eq!(
+            HttpRange::Suffix(1).normalize(size),
+            Some(OrderedRange::new(99..=99).unwrap())
+        );
+        assert_eq!(
+            HttpRange::Suffix(30).normalize(size),
+            Some(OrderedRange::new(70..=99).unwrap())
+        );
+        assert_eq!(
+            HttpRange::Suffix(101).normalize(size),
+            Some(OrderedRange::new(0..=99).unwrap())
+        );
+        assert_eq!(
+            HttpRange::Suffix(u64::MAX).normalize(NonZeroU64::MIN),
+            Some(OrderedRange::new(0..=0).unwrap())
+        );
+        assert_eq!(
+            HttpRange::Suffix(1).normalize(NonZeroU64::MAX),
+            Some(OrderedRange::new(u64::MAX - 1..=u64::MAX - 1).unwrap())
+        );
+    }
+
+    #[test]
+    fn align_down() {
+        let block = NonZeroU64::new(4096).unwrap();
+        let size = NonZeroU64::new(10_000).unwrap();
+
+        let aligned = HttpRange::Range(OrderedRange::new(5000..=5999).unwrap())
+            .align_down(block, size)
+            .unwrap();
+        assert_eq!(aligned, OrderedRange::new(4096..=5999).unwrap());
+        assert!(aligned.is_aligned(block));
+
+        assert_eq!(
+            HttpRange::Suffix(100).align_down(block, size),
+            Some(OrderedRange::new(8192..=9999).unwrap())
+        );
+        assert_eq!(
+            HttpRange::StartingPoint(4096).align_down(block, size),
+            Some(OrderedRange::new(4096..=9999).unwrap())
+        );
+        assert_eq!(
+            HttpRange::StartingPoint(10_000).align_down(block, size),
+            None
+        );
+    }
+
+    #[test]
+    fn from_fraction() {
+        let size = NonZeroU64::new(1000).unwrap();
+        assert_eq!(
+            HttpRange::from_fraction(0.5, 0.75, size).unwrap(),
+            HttpRange::Range(OrderedRange::new(500..=749).unwrap())
+        );
+        assert_eq!(
+            HttpRange::from_fraction(-1.0, 2.0, size).unwrap(),
+            HttpRange::Range(OrderedRange::new(0..=999).unwrap())
+        );
+        assert_eq!(
+            HttpRange::from_fraction(0.999, f64::INFINITY, size).unwrap(),
+            HttpRange::Range(OrderedRange::new(999..=999).unwrap())
+        );
+
+        assert_eq!(
+            HttpRange::from_fraction(0.75, 0.5, size).unwrap_err(),
+            InvalidFraction::Unordered {
+                start: 0.75,
+                end: 0.5
+            }
+        );
+        assert_eq!(
+            HttpRange::from_fraction(f64::NAN, 0.5, size).unwrap_err(),
+            InvalidFraction::NotANumber
+        );
+        for (start, end) in [(0.5, 0.5), (0.5, 0.5001), (1.0, 1.5)] {
+            assert_eq!(
+                HttpRange::from_fraction(start, end, size).unwrap_err(),
+                InvalidFraction::Empty,
+                "{start}..{end}"
+            );
+        }
+    }
+
+    #[test]
+    fn same_bytes() {
+        let size = NonZeroU64::new(100).unwrap();
+        let range = |range| HttpRange::Range(OrderedRange::new(range).unwrap());
+
+        assert!(range(0..=99).same_bytes(&HttpRange::Suffix(100), size));
+        assert!(HttpRange::StartingPoint(0).same_bytes(&HttpRange::Suffix(100), size));
+        assert!(HttpRange::StartingPoint(0).same_bytes(&HttpRange::Suffix(500), size));
+        assert!(range(90..=200).same_bytes(&HttpRange::Suffix(10), size));
+        assert!(range(90..=200).same_bytes(&HttpRange::StartingPoint(90), size));
+
+        assert!(!range(0..=98).same_bytes(&HttpRange::Suffix(100), size));
+        assert!(!HttpRange::StartingPoint(1).same_bytes(&HttpRange::Suffix(100), size));
+        assert!(!HttpRange::StartingPoint(100).same_bytes(&HttpRange::StartingPoint(100), size));
+    }
+
+    #[test]
+    fn is_satisfiable() {
+        let size = NonZeroU64::new(100).unwrap();
+
+        assert!(HttpRange::StartingPoint(99).is_satisfiable(size));
+        assert!(!HttpRange::StartingPoint(100).is_satisfiable(size));
+
+        assert!(HttpRange::Range(OrderedRange::new(99..=200).unwrap()).is_satisfiable(size));
+        assert!(!HttpRange::Range(OrderedRange::new(100..=200).unwrap()).is_satisfiable(size));
+
+        assert!(HttpRange::Suffix(1).is_satisfiable(size));
+        assert!(HttpRange::Suffix(500).is_satisfiable(size));
+        assert!(!HttpRange::Suffix(0).is_satisfiable(size));
+    }
 }
 
 #[cfg(test)]
 mod content_range_parsing_errors {
-    use crate::headers::{ParseHttpRangeOrContentRangeError, content_range::HttpContentRange};
+    use crate::headers::{
+        HeaderKind, ParseHttpRangeOrContentRangeError,
+        content_range::{Bound, HttpContentRange, bound_error},
+    };
 
     #[test]
     fn empty_input() {
         assert_eq!(
             "".parse::<HttpContentRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::Empty
+            ParseHttpRangeOrContentRangeError::Empty(HeaderKind::ContentRange)
         );
     }
 
+    #[test]
+    fn error_names_the_header() {
+        let err = "bytes10-20/50".parse::<HttpContentRange>().unwrap_err();
+        assert_eq!(err.header_kind(), HeaderKind::ContentRange);
+        assert_eq!(err.to_string(), "malformed Content-Range header value");
+    }
+
     #[test]
     fn missing_space() {
         assert_eq!(
             "bytes10-20/50".parse::<HttpContentRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::Malformed
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::ContentRange)
         );
     }
 
@@ -405,7 +1488,7 @@ mod content_range_parsing_errors {
     fn wrong_unit() {
         assert_eq!(
             "items 10-20/50".parse::<HttpContentRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::InvalidUnit
+            ParseHttpRangeOrContentRangeError::InvalidUnit(HeaderKind::ContentRange)
         );
     }
 
@@ -413,7 +1496,14 @@ mod content_range_parsing_errors {
     fn missing_slash() {
         assert_eq!(
             "bytes 10-20".parse::<HttpContentRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::Malformed
+            ParseHttpRangeOrContentRangeError::MissingSizeDelimiter(HeaderKind::ContentRange)
+        );
+        assert_eq!(
+            "bytes 0-99"
+                .parse::<HttpContentRange>()
+                .unwrap_err()
+                .to_string(),
+            "missing `/` before the size in Content-Range header value"
         );
     }
 
@@ -421,7 +1511,7 @@ mod content_range_parsing_errors {
     fn star_star() {
         assert_eq!(
             "bytes */*".parse::<HttpContentRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::Malformed
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::ContentRange)
         );
     }
 
@@ -429,7 +1519,7 @@ mod content_range_parsing_errors {
     fn end_exceeds_size() {
         assert_eq!(
             "bytes 10-20/15".parse::<HttpContentRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::MalformedRange
+            ParseHttpRangeOrContentRangeError::MalformedRange(HeaderKind::ContentRange)
         );
     }
 
@@ -437,45 +1527,396 @@ mod content_range_parsing_errors {
     fn end_equals_size() {
         assert_eq!(
             "bytes 0-20/20".parse::<HttpContentRange>().unwrap_err(),
-            ParseHttpRangeOrContentRangeError::MalformedRange
+            ParseHttpRangeOrContentRangeError::MalformedRange(HeaderKind::ContentRange)
         );
     }
 
     #[test]
-    fn end_at_boundary() {
-        assert!("bytes 0-19/20".parse::<HttpContentRange>().is_ok());
+    fn start_beyond_size() {
+        let err = "bytes 100-150/50".parse::<HttpContentRange>().unwrap_err();
+        assert_eq!(
+            err,
+            ParseHttpRangeOrContentRangeError::StartBeyondSize(HeaderKind::ContentRange)
+        );
+        assert_eq!(
+            err.to_string(),
+            "range starts beyond the size in Content-Range header value"
+        );
+        assert_eq!(
+            "bytes 50-60/50".parse::<HttpContentRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::StartBeyondSize(HeaderKind::ContentRange)
+        );
+        assert_eq!(
+            "bytes 49-60/50".parse::<HttpContentRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::MalformedRange(HeaderKind::ContentRange)
+        );
     }
-}
-
-#[cfg(test)]
-mod file_range {
-    use crate::{
-        file_range,
-        headers::{
-            OrderedRange,
-            content_range::{Bound, HttpContentRange},
-            range::HttpRange,
-        },
-    };
 
     #[test]
-    fn no_range_returns_full_file() {
-        let result = file_range(10, None).unwrap();
-        assert!(result.header().is_none());
-        assert_eq!(result.range(), &(0..10));
+    fn zero_size_bound() {
+        assert_eq!(
+            "bytes 0-0/0".parse::<HttpContentRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::StartBeyondSize(HeaderKind::ContentRange)
+        );
+        assert_eq!(
+            HttpContentRange::parse_lenient("bytes 0-0/0").unwrap_err(),
+            ParseHttpRangeOrContentRangeError::StartBeyondSize(HeaderKind::ContentRange)
+        );
+        assert_eq!(
+            Bound::new(0..=0, Some(0)).map_err(bound_error).unwrap_err(),
+            ParseHttpRangeOrContentRangeError::StartBeyondSize(HeaderKind::ContentRange)
+        );
+
+        // An empty representation can only be described as unsatisfiable.
+        assert!(
+            "bytes */0"
+                .parse::<HttpContentRange>()
+                .unwrap()
+                .is_unsatisfiable()
+        );
     }
 
     #[test]
-    fn starting_point_zero() {
-        let result = file_range(10, Some(HttpRange::StartingPoint(0))).unwrap();
-        assert!(result.header().is_some());
-        assert_eq!(result.range(), &(0..10));
+    fn parser_matches_bound_new() {
+        for (range, size) in [(0..=0, 0), (0..=9, 5), (10..=20, 5), (5..=5, 5), (0..=4, 5)] {
+            let value = format!("bytes {}-{}/{size}", range.start(), range.end());
+            assert_eq!(
+                value.parse::<HttpContentRange>(),
+                Bound::new(range, Some(size))
+                    .map(HttpContentRange::Bound)
+                    .map_err(bound_error),
+                "{value:?}"
+            );
+        }
     }
 
     #[test]
-    fn starting_point_middle() {
-        let result = file_range(10, Some(HttpRange::StartingPoint(5))).unwrap();
-        assert_eq!(result.range(), &(5..10));
+    fn end_at_boundary() {
+        assert!("bytes 0-19/20".parse::<HttpContentRange>().is_ok());
+    }
+
+    #[test]
+    fn empty_size() {
+        assert!(matches!(
+            "bytes 0-99/".parse::<HttpContentRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::InvalidSize(HeaderKind::ContentRange, _)
+        ));
+        assert_eq!(
+            HttpContentRange::parse_lenient("bytes 0-99/").unwrap(),
+            HttpContentRange::Bound(Bound::new(0..=99, None).unwrap())
+        );
+        assert_eq!(
+            HttpContentRange::parse_lenient("bytes */").unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::ContentRange)
+        );
+    }
+}
+
+#[cfg(test)]
+mod range_or_content_range {
+    use crate::headers::{
+        HeaderKind, OrderedRange, ParseHttpRangeOrContentRangeError,
+        content_range::{Bound, HttpContentRange},
+        range::HttpRange,
+        range_or_content_range::RangeOrContentRange,
+    };
+
+    #[test]
+    fn range_form() {
+        assert_eq!(
+            "bytes=0-99".parse::<RangeOrContentRange>().unwrap(),
+            RangeOrContentRange::Range(HttpRange::Range(OrderedRange::new(0..=99).unwrap()))
+        );
+    }
+
+    #[test]
+    fn content_range_form() {
+        assert_eq!(
+            "bytes 0-99/200".parse::<RangeOrContentRange>().unwrap(),
+            RangeOrContentRange::ContentRange(HttpContentRange::Bound(
+                Bound::new(0..=99, Some(200)).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn ambiguous_forms() {
+        assert_eq!(
+            "bytes=".parse::<RangeOrContentRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::MalformedRange(HeaderKind::Range)
+        );
+        assert_eq!(
+            "bytes ".parse::<RangeOrContentRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+        );
+        assert_eq!(
+            "bytes=0-99/200"
+                .parse::<RangeOrContentRange>()
+                .unwrap_err()
+                .header_kind(),
+            HeaderKind::Range
+        );
+        assert_eq!(
+            "bytes 0-99=200"
+                .parse::<RangeOrContentRange>()
+                .unwrap_err()
+                .header_kind(),
+            HeaderKind::ContentRange
+        );
+    }
+
+    #[test]
+    fn missing_separator() {
+        assert_eq!(
+            "bytes".parse::<RangeOrContentRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+        );
+    }
+}
+
+#[cfg(test)]
+mod range_unit {
+    use crate::headers::{
+        HeaderKind, ParseHttpRangeOrContentRangeError,
+        range_unit::{RangeUnit, WithUnit},
+    };
+
+    #[test]
+    fn of_range() {
+        assert_eq!(RangeUnit::of_range("bytes=0-99"), Ok(RangeUnit::Bytes));
+        assert_eq!(RangeUnit::of_range("BYTES=0-99"), Ok(RangeUnit::Bytes));
+        assert_eq!(
+            RangeUnit::of_range("Items=0-9, 20-"),
+            Ok(RangeUnit::Other("items".to_owned()))
+        );
+        assert_eq!(
+            RangeUnit::of_range("0-99"),
+            Err(ParseHttpRangeOrContentRangeError::Malformed(
+                HeaderKind::Range
+            ))
+        );
+        assert_eq!(
+            RangeUnit::of_range("=0-99"),
+            Err(ParseHttpRangeOrContentRangeError::InvalidUnit(
+                HeaderKind::Range
+            ))
+        );
+        assert_eq!(
+            RangeUnit::of_range("by tes=0-99"),
+            Err(ParseHttpRangeOrContentRangeError::InvalidUnit(
+                HeaderKind::Range
+            ))
+        );
+    }
+
+    #[test]
+    fn of_content_range() {
+        assert_eq!(
+            RangeUnit::of_content_range("bytes 0-99/200"),
+            Ok(RangeUnit::Bytes)
+        );
+        let unit = RangeUnit::of_content_range("pages 1-2/10").unwrap();
+        assert!(!unit.is_bytes());
+        assert_eq!(unit.to_string(), "pages");
+        assert_eq!(
+            RangeUnit::of_content_range(""),
+            Err(ParseHttpRangeOrContentRangeError::Empty(
+                HeaderKind::ContentRange
+            ))
+        );
+    }
+
+    #[test]
+    fn range_types_still_reject_other_units() {
+        use crate::headers::{content_range::HttpContentRange, range::HttpRange};
+
+        assert_eq!(
+            "items=0-9".parse::<HttpRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::InvalidUnit(HeaderKind::Range)
+        );
+        assert_eq!(
+            "pages 1-2/10".parse::<HttpContentRange>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::InvalidUnit(HeaderKind::ContentRange)
+        );
+        assert_eq!(
+            HttpRange::parse_strict("items=0-9").unwrap_err(),
+            ParseHttpRangeOrContentRangeError::InvalidUnit(HeaderKind::Range)
+        );
+    }
+
+    #[test]
+    fn parse_with_unit() {
+        use crate::headers::{
+            OrderedRange, content_range::HttpContentRange, range::HttpRange, ranges::HttpRanges,
+        };
+
+        let range = HttpRange::parse_with_unit("Items=0-9").unwrap();
+        assert_eq!(range.unit(), &RangeUnit::Other("items".to_owned()));
+        assert_eq!(
+            range.value(),
+            &HttpRange::Range(OrderedRange::new(0..=9).unwrap())
+        );
+        assert_eq!(
+            HttpRange::parse_with_unit("bytes=-5").map(WithUnit::into_parts),
+            Ok((RangeUnit::Bytes, HttpRange::Suffix(5)))
+        );
+        assert_eq!(
+            HttpRange::parse_with_unit("it ems=0-9").unwrap_err(),
+            ParseHttpRangeOrContentRangeError::InvalidUnit(HeaderKind::Range)
+        );
+        assert_eq!(
+            HttpRange::parse_with_unit("=0-9").unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+        );
+
+        let ranges = HttpRanges::parse_with_unit("pages=0-1, 4-").unwrap();
+        assert_eq!(ranges.unit(), &RangeUnit::Other("pages".to_owned()));
+        assert_eq!(ranges.value().len(), 2);
+
+        let content_range = HttpContentRange::parse_with_unit("items 0-9/10").unwrap();
+        assert_eq!(content_range.unit(), &RangeUnit::Other("items".to_owned()));
+        assert_eq!(content_range.value(), &"bytes 0-9/10".parse().unwrap());
+        assert_eq!(
+            HttpContentRange::parse_with_unit("bytes */10").map(|value| value.unit().clone()),
+            Ok(RangeUnit::Bytes)
+        );
+        assert_eq!(
+            HttpContentRange::parse_with_unit("i/tems 0-9/10").unwrap_err(),
+            ParseHttpRangeOrContentRangeError::InvalidUnit(HeaderKind::ContentRange)
+        );
+    }
+
+    #[test]
+    fn parse_with_unit_round_trips() {
+        use crate::headers::{
+            content_range::HttpContentRange, range::HttpRange, ranges::HttpRanges,
+        };
+
+        for value in ["items=0-9", "bytes=5-", "pages=-3"] {
+            let range = HttpRange::parse_with_unit(value).unwrap();
+            assert_eq!(range.to_string(), value);
+        }
+        let ranges = HttpRanges::parse_with_unit("pages=0-1, 4-").unwrap();
+        assert_eq!(ranges.to_string(), "pages=0-1, 4-");
+        for value in ["items 0-9/10", "items 0-9/*", "items */10"] {
+            let content_range = HttpContentRange::parse_with_unit(value).unwrap();
+            assert_eq!(content_range.to_string(), value);
+        }
+
+        #[cfg(feature = "http")]
+        assert_eq!(
+            http::HeaderValue::from(&HttpRange::parse_with_unit("items=0-9").unwrap()),
+            "items=0-9"
+        );
+    }
+}
+
+#[cfg(test)]
+mod file_range {
+    use crate::{
+        ContentRange, file_range, file_range_usize, file_range_with_policy,
+        headers::{
+            OrderedRange,
+            content_range::{Bound, HttpContentRange, Unsatisfiable},
+            range::HttpRange,
+        },
+    };
+
+    #[test]
+    fn resolved_ranges_always_produce_a_valid_bound() {
+        for size in [0, 1, 2, 10, u64::MAX] {
+            let positions = [0, 1, 9, 10, 11, size.saturating_sub(1), size, u64::MAX];
+            let mut ranges = vec![];
+            for &a in &positions {
+                ranges.push(HttpRange::StartingPoint(a));
+                ranges.push(HttpRange::Suffix(a));
+                for &b in &positions {
+                    if let Ok(range) = OrderedRange::new(a.min(b)..=a.max(b)) {
+                        ranges.push(HttpRange::Range(range));
+                    }
+                }
+            }
+
+            for range in ranges {
+                match file_range(size, Some(range)) {
+                    Ok(ContentRange {
+                        header: Some(HttpContentRange::Bound(bound)),
+                        range: served,
+                    }) => {
+                        assert_eq!(bound.size(), Some(size));
+                        assert_eq!(bound.range().start(), served.start);
+                        assert_eq!(bound.range().end() + 1, served.end);
+                        assert!(served.end <= size);
+                    }
+                    Ok(ContentRange {
+                        header: None,
+                        range: served,
+                    }) => {
+                        assert_eq!(size, 0, "{range:?}");
+                        assert_eq!(served, 0..0);
+                    }
+                    Ok(content_range) => panic!("unexpected {content_range:?}"),
+                    Err(unsatisfiable) => {
+                        assert_eq!(
+                            unsatisfiable.header(),
+                            HttpContentRange::Unsatisfiable(Unsatisfiable::new(size))
+                        );
+                        assert!(range.resolve(size).is_none(), "{range:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn no_range_returns_full_file() {
+        let result = file_range(10, None).unwrap();
+        assert!(result.header().is_none());
+        assert_eq!(result.range(), &(0..10));
+        assert_eq!(result.bounds(), (0, 10));
+    }
+
+    #[test]
+    fn usize_sizes() {
+        let (range, header) = file_range_usize(10, Some(HttpRange::Suffix(3))).unwrap();
+        assert_eq!(range, 7..10);
+        assert_eq!(
+            header,
+            Some(HttpContentRange::Bound(
+                Bound::new(7..=9, Some(10)).unwrap()
+            ))
+        );
+        assert_eq!(&b"0123456789"[range], b"789");
+
+        assert_eq!(file_range_usize(10, None).unwrap(), (0..10, None));
+        assert_eq!(
+            file_range_usize(10, Some(HttpRange::StartingPoint(10)))
+                .unwrap_err()
+                .header(),
+            HttpContentRange::Unsatisfiable(Unsatisfiable::new(10))
+        );
+    }
+
+    #[test]
+    fn bounds_are_half_open() {
+        let result = file_range(10, Some(HttpRange::Suffix(3))).unwrap();
+        assert_eq!(result.bounds(), (7, 10));
+
+        let empty = file_range(0, None).unwrap();
+        assert_eq!(empty.bounds(), (0, 0));
+    }
+
+    #[test]
+    fn starting_point_zero() {
+        let result = file_range(10, Some(HttpRange::StartingPoint(0))).unwrap();
+        assert!(result.header().is_some());
+        assert_eq!(result.range(), &(0..10));
+    }
+
+    #[test]
+    fn starting_point_middle() {
+        let result = file_range(10, Some(HttpRange::StartingPoint(5))).unwrap();
+        assert_eq!(result.range(), &(5..10));
     }
 
     #[test]
@@ -484,268 +1925,2037 @@ mod file_range {
         assert_eq!(result.range(), &(9..10));
     }
 
-    #[test]
-    fn starting_point_at_size() {
-        let result = file_range(10, Some(HttpRange::StartingPoint(10)));
-        assert!(result.is_err());
+    #[test]
+    fn starting_point_at_size() {
+        let result = file_range(10, Some(HttpRange::StartingPoint(10)));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn starting_point_beyond_size() {
+        let result = file_range(10, Some(HttpRange::StartingPoint(20)));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn range_single_byte() {
+        let range = HttpRange::Range(OrderedRange::new(0..=0).unwrap());
+        let result = file_range(10, Some(range)).unwrap();
+        assert_eq!(result.range(), &(0..1));
+    }
+
+    #[test]
+    fn range_full_file() {
+        let range = HttpRange::Range(OrderedRange::new(0..=9).unwrap());
+        let result = file_range(10, Some(range)).unwrap();
+        assert_eq!(result.range(), &(0..10));
+    }
+
+    #[test]
+    fn range_end_at_size_is_clamped() {
+        let range = HttpRange::Range(OrderedRange::new(0..=10).unwrap());
+        let result = file_range(10, Some(range)).unwrap();
+        assert_eq!(result.range(), &(0..10));
+    }
+
+    #[test]
+    fn range_end_at_last_byte_header() {
+        let range = "bytes=0-99".parse::<HttpRange>().unwrap();
+        let result = file_range(100, Some(range)).unwrap();
+        assert_eq!(result.range(), &(0..100));
+        assert_eq!(
+            result.header(),
+            Some(HttpContentRange::Bound(
+                Bound::new(0..=99, Some(100)).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn range_end_at_size_header_is_clamped() {
+        let range = "bytes=0-100".parse::<HttpRange>().unwrap();
+        let result = file_range(100, Some(range)).unwrap();
+        assert_eq!(result.range(), &(0..100));
+        assert_eq!(
+            result.header(),
+            Some(HttpContentRange::Bound(
+                Bound::new(0..=99, Some(100)).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn range_beyond_size_is_clamped() {
+        let range = HttpRange::Range(OrderedRange::new(0..=50).unwrap());
+        let result = file_range(10, Some(range)).unwrap();
+        assert_eq!(result.range(), &(0..10));
+    }
+
+    #[test]
+    fn range_end_at_u64_max_is_clamped() {
+        let range = HttpRange::Range(OrderedRange::new(0..=u64::MAX).unwrap());
+        let result = file_range(10, Some(range)).unwrap();
+        assert_eq!(result.range(), &(0..10));
+    }
+
+    #[test]
+    fn range_start_at_size_is_unsatisfiable() {
+        let range = HttpRange::Range(OrderedRange::new(10..=20).unwrap());
+        let result = file_range(10, Some(range));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn range_start_beyond_size_is_unsatisfiable() {
+        let range = HttpRange::Range(OrderedRange::new(50..=100).unwrap());
+        let result = file_range(10, Some(range));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn starting_point_at_u64_max_is_unsatisfiable() {
+        let range = "bytes=18446744073709551615-".parse::<HttpRange>().unwrap();
+        let result = file_range(10, Some(range)).unwrap_err();
+        assert_eq!(
+            result.header(),
+            HttpContentRange::Unsatisfiable(Unsatisfiable::new(10))
+        );
+    }
+
+    #[test]
+    fn range_at_u64_max_is_unsatisfiable() {
+        let range = "bytes=18446744073709551615-18446744073709551615"
+            .parse::<HttpRange>()
+            .unwrap();
+        let result = file_range(10, Some(range)).unwrap_err();
+        assert_eq!(
+            result.header(),
+            HttpContentRange::Unsatisfiable(Unsatisfiable::new(10))
+        );
+    }
+
+    #[test]
+    fn suffix_entire_file() {
+        let result = file_range(10, Some(HttpRange::Suffix(10))).unwrap();
+        assert_eq!(result.range(), &(0..10));
+    }
+
+    #[test]
+    fn suffix_last_byte() {
+        let result = file_range(10, Some(HttpRange::Suffix(1))).unwrap();
+        assert_eq!(result.range(), &(9..10));
+    }
+
+    #[test]
+    fn suffix_middle() {
+        let result = file_range(10, Some(HttpRange::Suffix(5))).unwrap();
+        assert_eq!(result.range(), &(5..10));
+    }
+
+    #[test]
+    fn suffix_zero_is_unsatisfiable() {
+        let result = file_range(10, Some(HttpRange::Suffix(0)));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn suffix_exceeds_size_is_clamped() {
+        let result = file_range(10, Some(HttpRange::Suffix(11))).unwrap();
+        assert_eq!(result.range(), &(0..10));
+    }
+
+    #[test]
+    fn size_one_no_range() {
+        let result = file_range(1, None).unwrap();
+        assert_eq!(result.range(), &(0..1));
+    }
+
+    #[test]
+    fn size_one_starting_point_zero() {
+        let result = file_range(1, Some(HttpRange::StartingPoint(0))).unwrap();
+        assert_eq!(result.range(), &(0..1));
+    }
+
+    #[test]
+    fn size_one_suffix_one() {
+        let result = file_range(1, Some(HttpRange::Suffix(1))).unwrap();
+        assert_eq!(result.range(), &(0..1));
+    }
+
+    #[test]
+    fn content_range_header_present_for_range_request() {
+        let result = file_range(10, Some(HttpRange::StartingPoint(0))).unwrap();
+        let header = result.header().unwrap();
+        assert_eq!(
+            header,
+            HttpContentRange::Bound(Bound::new(0..=9, Some(10)).unwrap())
+        );
+    }
+
+    #[test]
+    fn content_range_new() {
+        let header = HttpContentRange::Bound(Bound::new(5..=9, Some(10)).unwrap());
+        let content_range = ContentRange::new(Some(header), 5..10).unwrap();
+        assert_eq!(content_range.header(), Some(header));
+        assert_eq!(content_range.range(), &(5..10));
+
+        let empty = ContentRange::new(None, 0..0).unwrap();
+        assert_eq!(empty.range(), &(0..0));
+    }
+
+    #[test]
+    fn content_range_new_rejects_unordered_range() {
+        assert!(ContentRange::new(None, 10..5).is_err());
+    }
+
+    #[test]
+    fn content_range_from_bound_round_trips() {
+        let bound = Bound::new(5..=9, Some(10)).unwrap();
+        let content_range = ContentRange::from_bound(bound).unwrap();
+        assert_eq!(content_range.range(), &(5..10));
+        assert_eq!(
+            content_range,
+            file_range(10, Some(HttpRange::Suffix(5))).unwrap()
+        );
+    }
+
+    #[test]
+    fn content_range_from_single_byte_bound_is_half_open() {
+        let content_range = ContentRange::from_bound(Bound::new(0..=0, Some(10)).unwrap()).unwrap();
+        assert_eq!(content_range.range(), &(0..1));
+    }
+
+    #[test]
+    fn content_range_from_bound_ending_at_u64_max() {
+        let bound = Bound::new(0..=u64::MAX, None).unwrap();
+        assert_eq!(ContentRange::from_bound(bound), None);
+
+        let bound = Bound::new(0..=u64::MAX - 1, None).unwrap();
+        let content_range = ContentRange::from_bound(bound).unwrap();
+        assert_eq!(content_range.range(), &(0..u64::MAX));
+    }
+
+    #[test]
+    fn content_range_try_merge() {
+        let first = ContentRange::from_bound(Bound::new(0..=99, Some(200)).unwrap()).unwrap();
+        let second = ContentRange::from_bound(Bound::new(100..=199, Some(200)).unwrap()).unwrap();
+        let merged = ContentRange::from_bound(Bound::new(0..=199, Some(200)).unwrap()).unwrap();
+        assert_eq!(first.try_merge(&second), Some(merged.clone()));
+        assert_eq!(second.try_merge(&first), Some(merged));
+
+        let unknown_size = ContentRange::from_bound(Bound::new(0..=9, None).unwrap()).unwrap();
+        let next = ContentRange::from_bound(Bound::new(10..=19, None).unwrap()).unwrap();
+        assert_eq!(
+            unknown_size.try_merge(&next),
+            Some(ContentRange::from_bound(Bound::new(0..=19, None).unwrap()).unwrap())
+        );
+    }
+
+    #[test]
+    fn content_range_try_merge_rejects_non_contiguous() {
+        let first = ContentRange::from_bound(Bound::new(0..=99, Some(200)).unwrap()).unwrap();
+
+        let gap = ContentRange::from_bound(Bound::new(101..=199, Some(200)).unwrap()).unwrap();
+        assert_eq!(first.try_merge(&gap), None);
+
+        let overlap = ContentRange::from_bound(Bound::new(99..=199, Some(200)).unwrap()).unwrap();
+        assert_eq!(first.try_merge(&overlap), None);
+
+        let other_size =
+            ContentRange::from_bound(Bound::new(100..=199, Some(300)).unwrap()).unwrap();
+        assert_eq!(first.try_merge(&other_size), None);
+
+        let full = file_range(100, None).unwrap();
+        assert_eq!(first.try_merge(&full), None);
+    }
+
+    #[test]
+    fn policy_ignores_range_below_threshold() {
+        let range = Some(HttpRange::StartingPoint(5));
+        let result = file_range_with_policy(10, range, 11).unwrap();
+        assert!(result.header().is_none());
+        assert_eq!(result.range(), &(0..10));
+    }
+
+    #[test]
+    fn policy_ignores_unsatisfiable_range_below_threshold() {
+        let range = Some(HttpRange::StartingPoint(50));
+        let result = file_range_with_policy(10, range, 11).unwrap();
+        assert!(result.header().is_none());
+    }
+
+    #[test]
+    fn policy_applies_range_at_threshold() {
+        let range = Some(HttpRange::StartingPoint(5));
+        let result = file_range_with_policy(10, range, 10).unwrap();
+        assert!(result.header().is_some());
+        assert_eq!(result.range(), &(5..10));
+    }
+
+    #[test]
+    fn size_zero_no_range() {
+        let result = file_range(0, None).unwrap();
+        assert!(result.header().is_none());
+        assert_eq!(result.range(), &(0..0));
+    }
+
+    #[test]
+    fn size_zero_starting_point_is_unsatisfiable() {
+        let result = file_range(0, Some(HttpRange::StartingPoint(0)));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn size_zero_range_is_unsatisfiable() {
+        let range = HttpRange::Range(OrderedRange::new(0..=0).unwrap());
+        let result = file_range(0, Some(range));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn size_zero_suffix_serves_full_response() {
+        let result = file_range(0, Some(HttpRange::Suffix(1))).unwrap();
+        assert!(result.header().is_none());
+        assert_eq!(result.range(), &(0..0));
+    }
+
+    #[test]
+    fn size_zero_suffix_zero_is_unsatisfiable() {
+        let result = file_range(0, Some(HttpRange::Suffix(0)));
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod serve_file {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    #[cfg(feature = "http")]
+    use http::{HeaderValue, StatusCode};
+
+    use crate::{
+        BodyRange, DEFAULT_MAX_RANGES, DEFAULT_MIN_RANGE_SIZE, DEFAULT_TINY_RANGE_RATIO,
+        RangeConfig, RangeResponseKind, ServedBody,
+        headers::{
+            DEFAULT_MAX_HEADER_LEN,
+            accept_ranges::AcceptRanges,
+            content_range::{Bound, HttpContentRange, Unsatisfiable},
+            range::HttpRange,
+        },
+        serve_file_with_accept_ranges, serve_file_with_config, serve_file_with_http_range,
+        serve_file_with_http_range_or_else, serve_file_with_http_range_windowed,
+        serve_file_with_response_kind,
+    };
+
+    #[test]
+    fn no_range_returns_full_body() {
+        let body = Bytes::from_static(b"hello world");
+        let result = serve_file_with_http_range(body.clone(), None).unwrap();
+        assert_eq!(result.body(), &body);
+        assert!(result.header().is_none());
+    }
+
+    #[test]
+    fn or_else_calls_on_full_only_without_range() {
+        let body = Bytes::from_static(b"hello world");
+
+        let mut calls = 0;
+        let result = serve_file_with_http_range_or_else(body.clone(), None, |full| {
+            calls += 1;
+            full.len()
+        });
+        assert_eq!(result, Ok(ServedBody::Full(11)));
+        assert_eq!(calls, 1);
+
+        let mut calls = 0;
+        let result =
+            serve_file_with_http_range_or_else(body.clone(), Some(HttpRange::Suffix(5)), |_| {
+                calls += 1;
+            });
+        let Ok(ServedBody::Range(body_range)) = result else {
+            panic!("expected a range, got {result:?}");
+        };
+        assert_eq!(body_range.body(), &Bytes::from_static(b"world"));
+        assert_eq!(calls, 0);
+
+        let result =
+            serve_file_with_http_range_or_else(body, Some(HttpRange::StartingPoint(20)), |_| {
+                unreachable!("the range is not satisfiable")
+            });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn range_slices_body() {
+        let body = Bytes::from_static(b"hello world");
+        let range = Some(HttpRange::StartingPoint(6));
+        let result = serve_file_with_http_range(body, range).unwrap();
+        assert_eq!(result.body(), &Bytes::from_static(b"world"));
+        assert!(result.header().is_some());
+    }
+
+    #[test]
+    fn suffix_slices_from_end() {
+        let body = Bytes::from_static(b"hello world");
+        let range = Some(HttpRange::Suffix(5));
+        let result = serve_file_with_http_range(body, range).unwrap();
+        assert_eq!(result.body(), &Bytes::from_static(b"world"));
+    }
+
+    #[test]
+    fn empty_body_returns_empty_response() {
+        let body = Bytes::new();
+        let result = serve_file_with_http_range(body, None).unwrap();
+        assert!(result.body().is_empty());
+        assert!(result.header().is_none());
+    }
+
+    #[test]
+    fn suffix_zero_is_unsatisfiable() {
+        let body = Bytes::from_static(b"hello");
+        let result = serve_file_with_http_range(body, Some(HttpRange::Suffix(0)));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn windowed_range_is_relative_to_the_window() {
+        let blob = Bytes::from_static(b"first filesecond file");
+        let result =
+            serve_file_with_http_range_windowed(blob.clone(), 10..21, Some(HttpRange::Suffix(4)))
+                .unwrap();
+        assert_eq!(result.body(), &Bytes::from_static(b"file"));
+        assert_eq!(
+            result.header(),
+            Some(HttpContentRange::Bound(
+                Bound::new(7..=10, Some(11)).unwrap()
+            ))
+        );
+
+        let result = serve_file_with_http_range_windowed(blob.clone(), 0..10, None).unwrap();
+        assert_eq!(result.body(), &Bytes::from_static(b"first file"));
+
+        // Valid in the blob, but beyond the end of the window.
+        let result =
+            serve_file_with_http_range_windowed(blob, 0..10, Some(HttpRange::StartingPoint(15)));
+        assert_eq!(
+            result.unwrap_err().header(),
+            HttpContentRange::Unsatisfiable(Unsatisfiable::new(10))
+        );
+    }
+
+    #[test]
+    fn response_kind() {
+        let body = Bytes::from_static(b"hello world");
+        for (range, kind) in [
+            (None, RangeResponseKind::Full),
+            (Some(HttpRange::Suffix(5)), RangeResponseKind::Partial),
+            (
+                Some(HttpRange::StartingPoint(20)),
+                RangeResponseKind::Unsatisfiable,
+            ),
+        ] {
+            let (result, response_kind) = serve_file_with_response_kind(body.clone(), range);
+            assert_eq!(result, serve_file_with_http_range(body.clone(), range));
+            assert_eq!(response_kind, kind);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn response_kind_status_code() {
+        assert_eq!(RangeResponseKind::Full.status_code(), StatusCode::OK);
+        assert_eq!(
+            RangeResponseKind::Partial.status_code(),
+            StatusCode::PARTIAL_CONTENT
+        );
+        assert_eq!(
+            RangeResponseKind::Unsatisfiable.status_code(),
+            StatusCode::RANGE_NOT_SATISFIABLE
+        );
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(AcceptRanges::default(), AcceptRanges::Bytes);
+        assert_eq!(
+            RangeConfig::default(),
+            RangeConfig {
+                max_ranges: DEFAULT_MAX_RANGES,
+                max_header_len: DEFAULT_MAX_HEADER_LEN,
+                min_range_size: DEFAULT_MIN_RANGE_SIZE,
+                tiny_range_ratio: DEFAULT_TINY_RANGE_RATIO,
+            }
+        );
+    }
+
+    #[test]
+    fn tiny_range_ratio() {
+        let config = RangeConfig::default();
+        assert!(config.is_tiny_range(1, 1024 * 1024 * 1024));
+        assert!(!config.is_tiny_range(1, 1024 * 1024));
+        assert!(!config.is_tiny_range(1024, 1024 * 1024 * 1024));
+        assert!(!config.is_tiny_range(u64::MAX, u64::MAX));
+
+        let disabled = RangeConfig {
+            tiny_range_ratio: 0,
+            ..RangeConfig::default()
+        };
+        assert!(!disabled.is_tiny_range(1, u64::MAX));
+    }
+
+    #[test]
+    fn serve_with_config() {
+        let body = Bytes::from_static(b"hello world");
+        let config = RangeConfig {
+            tiny_range_ratio: 2,
+            ..RangeConfig::default()
+        };
+        let result = serve_file_with_config(body.clone(), Some(HttpRange::Suffix(1)), &config);
+        assert_eq!(result.unwrap().body(), &Bytes::from_static(b"d"));
+
+        let config = RangeConfig {
+            min_range_size: 12,
+            ..RangeConfig::default()
+        };
+        let result = serve_file_with_config(body.clone(), Some(HttpRange::Suffix(1)), &config);
+        let result = result.unwrap();
+        assert_eq!(result.body(), &body);
+        assert!(result.header().is_none());
+    }
+
+    #[test]
+    fn accept_ranges_none_serves_full_body() {
+        let body = Bytes::from_static(b"hello world");
+        let range = Some(HttpRange::Suffix(5));
+        let result =
+            serve_file_with_accept_ranges(body.clone(), range, AcceptRanges::None).unwrap();
+        assert_eq!(result.body(), &body);
+        assert!(result.header().is_none());
+        assert_eq!(result.accept_ranges(), AcceptRanges::None);
+    }
+
+    #[test]
+    fn body_range_new_matches_served_range() {
+        let body = Bytes::from_static(b"hello world");
+        let served = serve_file_with_http_range(body, Some(HttpRange::StartingPoint(6))).unwrap();
+
+        let header = HttpContentRange::Bound(Bound::new(6..=10, Some(11)).unwrap());
+        let built = BodyRange::new(Bytes::from_static(b"world"), Some(header));
+        assert_eq!(built, served);
+    }
+
+    #[test]
+    fn u64_max_range_is_unsatisfiable() {
+        let body = Bytes::from_static(b"hello");
+        let range = "bytes=18446744073709551615-18446744073709551615"
+            .parse::<HttpRange>()
+            .unwrap();
+        assert!(serve_file_with_http_range(body, Some(range)).is_err());
+    }
+
+    #[test]
+    fn empty_body_with_suffix_returns_empty_response() {
+        let body = Bytes::new();
+        let result = serve_file_with_http_range(body, Some(HttpRange::Suffix(5))).unwrap();
+        assert!(result.body().is_empty());
+        assert!(result.header().is_none());
+    }
+
+    #[test]
+    fn arc_slice_shares_the_data() {
+        let body = Arc::<[u8]>::from(&b"hello world"[..]);
+        let result =
+            serve_file_with_http_range(Arc::clone(&body), Some(HttpRange::Suffix(5))).unwrap();
+        assert_eq!(&**result.body(), b"world");
+        assert_eq!(result.body().range(), 6..11);
+        assert!(Arc::ptr_eq(result.body().data(), &body));
+        assert_eq!(
+            result.header(),
+            Some(HttpContentRange::Bound(
+                Bound::new(6..=10, Some(11)).unwrap()
+            ))
+        );
+
+        let result = serve_file_with_http_range(Arc::<[u8]>::from(Vec::new()), None).unwrap();
+        assert!(result.body().is_empty());
+    }
+
+    #[test]
+    fn into_parts_returns_body_and_header() {
+        let body = Bytes::from_static(b"hello world");
+        let served = serve_file_with_http_range(body, Some(HttpRange::StartingPoint(6))).unwrap();
+
+        let (body, header) = served.into_parts();
+        assert_eq!(body, Bytes::from_static(b"world"));
+        assert_eq!(
+            header,
+            Some(HttpContentRange::Bound(
+                Bound::new(6..=10, Some(11)).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn precompressed_ranges_apply_to_encoded_bytes() {
+        use crate::serve_precompressed;
+
+        let gzip = HeaderValue::from_static("gzip");
+        let encoded = Bytes::from_static(b"\x1f\x8b\x08\x00compressed");
+
+        let served =
+            serve_precompressed(encoded.clone(), gzip.clone(), Some(HttpRange::Suffix(10)))
+                .unwrap();
+        assert_eq!(served.body(), &Bytes::from_static(b"compressed"));
+        assert_eq!(served.content_encoding(), Some(&gzip));
+        assert_eq!(
+            served.header(),
+            Some(HttpContentRange::Bound(
+                Bound::new(4..=13, Some(14)).unwrap()
+            ))
+        );
+
+        let served = serve_precompressed(encoded, gzip.clone(), None).unwrap();
+        assert_eq!(served.content_encoding(), Some(&gzip));
+
+        let plain = serve_file_with_http_range(Bytes::from_static(b"plain"), None).unwrap();
+        assert_eq!(plain.content_encoding(), None);
+    }
+}
+
+#[cfg(test)]
+mod ranges {
+    use std::num::NonZeroU64;
+
+    use crate::headers::{
+        DEFAULT_MAX_HEADER_LEN, HeaderKind, OrderedRange, ParseHttpRangeOrContentRangeError,
+        range::HttpRange, ranges::HttpRanges,
+    };
+
+    #[test]
+    fn successful_parsing() {
+        let ranges = "bytes=0-9, 20-,-5".parse::<HttpRanges>().unwrap();
+        assert_eq!(
+            ranges.iter().copied().collect::<Vec<_>>(),
+            [
+                HttpRange::Range(OrderedRange::new(0..=9).unwrap()),
+                HttpRange::StartingPoint(20),
+                HttpRange::Suffix(5),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_range_parsing() {
+        let ranges = "bytes=0-9".parse::<HttpRanges>().unwrap();
+        assert_eq!(ranges.len(), 1);
+    }
+
+    #[test]
+    fn empty_piece_rejected() {
+        assert_eq!(
+            "bytes=0-9,,10-19".parse::<HttpRanges>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+        );
+    }
+
+    #[test]
+    fn leading_and_trailing_commas() {
+        for value in ["bytes=0-99,", "bytes=,0-99"] {
+            assert_eq!(
+                value.parse::<HttpRanges>().unwrap_err(),
+                ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+            );
+            assert_eq!(
+                HttpRanges::parse_strict(value).unwrap_err(),
+                ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+            );
+            assert_eq!(
+                HttpRanges::parse_lenient(value).unwrap(),
+                "bytes=0-99".parse::<HttpRanges>().unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn too_long_rejected() {
+        let mut value = String::from("bytes=0-0");
+        while value.len() <= 16 * 1024 {
+            value.push_str(", 0-0");
+        }
+
+        assert_eq!(
+            value.parse::<HttpRanges>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::TooLong(HeaderKind::Range, DEFAULT_MAX_HEADER_LEN)
+        );
+        assert_eq!(
+            HttpRanges::parse_with_max_len(&value, 8).unwrap_err(),
+            ParseHttpRangeOrContentRangeError::TooLong(HeaderKind::Range, 8)
+        );
+        assert!(HttpRanges::parse_with_max_len(&value, 32 * 1024).is_ok());
+        assert_eq!(
+            HttpRange::parse_with_max_len("bytes=0-99", 9).unwrap_err(),
+            ParseHttpRangeOrContentRangeError::TooLong(HeaderKind::Range, 9)
+        );
+        assert!(HttpRange::parse_with_max_len("bytes=0-99", 10).is_ok());
+    }
+
+    #[test]
+    fn parse_with_config() {
+        use crate::RangeConfig;
+
+        let config = RangeConfig {
+            max_ranges: 2,
+            max_header_len: 16,
+            ..RangeConfig::default()
+        };
+        assert_eq!(
+            HttpRanges::parse_with_config("bytes=0-1, 4-5", &config).map(|ranges| ranges.len()),
+            Ok(2)
+        );
+        assert_eq!(
+            HttpRanges::parse_with_config("bytes=0-1,4-5,8-", &config).unwrap_err(),
+            ParseHttpRangeOrContentRangeError::TooManyRanges(HeaderKind::Range, 2)
+        );
+        assert_eq!(
+            HttpRanges::parse_with_config("bytes=0-1, 4-5, 8-", &config).unwrap_err(),
+            ParseHttpRangeOrContentRangeError::TooLong(HeaderKind::Range, 16)
+        );
+    }
+
+    #[test]
+    fn lenient_without_ranges_rejected() {
+        assert_eq!(
+            HttpRanges::parse_lenient("bytes=, ,").unwrap_err(),
+            ParseHttpRangeOrContentRangeError::Malformed(HeaderKind::Range)
+        );
+    }
+
+    #[test]
+    fn try_from_vec_display() {
+        let ranges = HttpRanges::try_from(vec![
+            HttpRange::Range(OrderedRange::new(0..=9).unwrap()),
+            HttpRange::Range(OrderedRange::new(100..=199).unwrap()),
+            HttpRange::StartingPoint(500),
+            HttpRange::Suffix(5),
+        ])
+        .unwrap();
+        assert_eq!(ranges.to_string(), "bytes=0-9, 100-199, 500-, -5");
+        assert_eq!(ranges, "bytes=0-9, 100-199, 500-, -5".parse().unwrap());
+
+        let single = HttpRanges::try_from(vec![HttpRange::Suffix(5)]).unwrap();
+        assert_eq!(single.to_string(), "bytes=-5");
+    }
+
+    #[test]
+    fn empty_ranges_cannot_be_built() {
+        use crate::headers::ranges::EmptyRanges;
+
+        assert_eq!(HttpRanges::try_from(Vec::new()), Err(EmptyRanges));
+        assert!("bytes=".parse::<HttpRanges>().is_err());
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for value in [
+            "bytes=0-9, 100-199, -50",
+            "bytes=-50, 200-, 0-0",
+            "bytes=200-, -50, 10-20",
+            "bytes=0-",
+        ] {
+            let ranges = value.parse::<HttpRanges>().unwrap();
+            assert_eq!(ranges.to_string(), value);
+            assert_eq!(ranges.to_string().parse::<HttpRanges>().unwrap(), ranges);
+        }
+
+        let ranges = "BYTES=-50,200-,  0-9".parse::<HttpRanges>().unwrap();
+        assert_eq!(ranges.to_string(), "bytes=-50, 200-, 0-9");
+    }
+
+    #[test]
+    fn wrong_unit() {
+        assert_eq!(
+            "items=0-9, 10-19".parse::<HttpRanges>().unwrap_err(),
+            ParseHttpRangeOrContentRangeError::InvalidUnit(HeaderKind::Range)
+        );
+    }
+
+    #[test]
+    fn dedup_removes_identical_ranges() {
+        let mut ranges = "bytes=10-19, 0-9, 10-19, 0-9, 10-"
+            .parse::<HttpRanges>()
+            .unwrap();
+        ranges.dedup();
+        assert_eq!(
+            ranges.iter().copied().collect::<Vec<_>>(),
+            [
+                HttpRange::Range(OrderedRange::new(10..=19).unwrap()),
+                HttpRange::Range(OrderedRange::new(0..=9).unwrap()),
+                HttpRange::StartingPoint(10),
+            ]
+        );
+
+        let mut ranges = "bytes=0-9, 0-9, 10-19".parse::<HttpRanges>().unwrap();
+        ranges.dedup();
+        assert_eq!(ranges.len(), 2);
+    }
+
+    #[test]
+    fn coalesce_merges_overlapping_and_adjacent() {
+        let ranges = "bytes=50-59, 0-9, 5-19, 20-29"
+            .parse::<HttpRanges>()
+            .unwrap();
+        assert_eq!(
+            ranges.coalesce(100),
+            [
+                OrderedRange::new(0..=29).unwrap(),
+                OrderedRange::new(50..=59).unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn coalesce_with_provenance() {
+        let ranges = "bytes=50-59, 0-9, 200-, 5-19, 20-29, -10"
+            .parse::<HttpRanges>()
+            .unwrap();
+        let coalesced = ranges.coalesce_with_provenance(100);
+        assert_eq!(
+            coalesced,
+            [
+                (OrderedRange::new(0..=29).unwrap(), vec![1, 3, 4]),
+                (OrderedRange::new(50..=59).unwrap(), vec![0]),
+                (OrderedRange::new(90..=99).unwrap(), vec![5]),
+            ]
+        );
+        assert_eq!(
+            coalesced
+                .into_iter()
+                .map(|(range, _)| range)
+                .collect::<Vec<_>>(),
+            ranges.coalesce(100)
+        );
+    }
+
+    #[test]
+    fn first_satisfiable_skips_unsatisfiable() {
+        let ranges = "bytes=200-, 10-19, 0-9".parse::<HttpRanges>().unwrap();
+        let content_range = ranges
+            .first_satisfiable(NonZeroU64::new(100).unwrap())
+            .unwrap();
+        assert_eq!(content_range.range(), &(10..20));
+    }
+
+    #[test]
+    fn first_satisfiable_none() {
+        let ranges = "bytes=200-, -0".parse::<HttpRanges>().unwrap();
+        assert!(
+            ranges
+                .first_satisfiable(NonZeroU64::new(100).unwrap())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn count_satisfiable() {
+        let size = NonZeroU64::new(100).unwrap();
+
+        let ranges = "bytes=200-, 0-9, -0, 90-120, -5"
+            .parse::<HttpRanges>()
+            .unwrap();
+        assert_eq!(ranges.count_satisfiable(size), 3);
+
+        let ranges = "bytes=0-9, 0-9".parse::<HttpRanges>().unwrap();
+        assert_eq!(ranges.count_satisfiable(size), 2);
+
+        let ranges = "bytes=100-, -0".parse::<HttpRanges>().unwrap();
+        assert_eq!(ranges.count_satisfiable(size), 0);
+    }
+
+    #[test]
+    fn coalesce_drops_unsatisfiable() {
+        let ranges = "bytes=200-, 0-9, -0".parse::<HttpRanges>().unwrap();
+        assert_eq!(ranges.coalesce(100), [OrderedRange::new(0..=9).unwrap()]);
+    }
+}
+
+#[cfg(test)]
+mod serve_file_ranges {
+    use bytes::Bytes;
+
+    use crate::{
+        ServeRangesError,
+        headers::{
+            content_range::{Bound, HttpContentRange},
+            ranges::HttpRanges,
+        },
+        serve_file_with_http_ranges,
+    };
+
+    #[test]
+    fn config_limits_ignore_ranges() {
+        use crate::{RangeConfig, serve_file_with_http_ranges_and_config};
+
+        let body = Bytes::from_static(b"hello world");
+        let config = RangeConfig {
+            max_ranges: 2,
+            ..RangeConfig::default()
+        };
+
+        let ranges = "bytes=0-1, 4-5".parse::<HttpRanges>().ok();
+        let result =
+            serve_file_with_http_ranges_and_config(body.clone(), ranges, 11, &config).unwrap();
+        assert_eq!(result.len(), 2);
+
+        let ranges = "bytes=0-1, 4-5, 8-".parse::<HttpRanges>().ok();
+        let result =
+            serve_file_with_http_ranges_and_config(body.clone(), ranges, 11, &config).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].body(), &body);
+        assert!(result[0].header().is_none());
+
+        let config = RangeConfig {
+            min_range_size: 12,
+            ..RangeConfig::default()
+        };
+        let ranges = "bytes=0-1".parse::<HttpRanges>().ok();
+        let result =
+            serve_file_with_http_ranges_and_config(body.clone(), ranges, 11, &config).unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(result[0].header().is_none());
+    }
+
+    #[test]
+    fn error_display() {
+        let body = Bytes::from_static(b"hello world");
+        let ranges = "bytes=20-".parse::<HttpRanges>().ok();
+        let err = serve_file_with_http_ranges(body, ranges, 11).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "none of the requested ranges is satisfiable"
+        );
+
+        let err = ServeRangesError::TooManyBytes {
+            total_bytes: 12,
+            max_total_bytes: 11,
+        };
+        assert_eq!(
+            err.to_string(),
+            "requested ranges add up to 12 bytes, more than the allowed 11"
+        );
+        let _: &dyn std::error::Error = &err;
+    }
+
+    #[test]
+    fn no_range_returns_full_body() {
+        let body = Bytes::from_static(b"hello world");
+        let result = serve_file_with_http_ranges(body.clone(), None, 11).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].body(), &body);
+        assert!(result[0].header().is_none());
+    }
+
+    #[test]
+    fn serves_each_coalesced_range() {
+        let body = Bytes::from_static(b"hello world");
+        let ranges = "bytes=0-1, 2-4, -5".parse::<HttpRanges>().ok();
+        let result = serve_file_with_http_ranges(body, ranges, 11).unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].body(), &Bytes::from_static(b"hello"));
+        assert_eq!(
+            result[0].header(),
+            Some(HttpContentRange::Bound(
+                Bound::new(0..=4, Some(11)).unwrap()
+            ))
+        );
+        assert_eq!(result[1].body(), &Bytes::from_static(b"world"));
+    }
+
+    #[test]
+    fn overlapping_ranges_are_limited_after_coalescing() {
+        let body = Bytes::from_static(b"hello world");
+        let ranges = "bytes=0-7, 3-10, -8".parse::<HttpRanges>().unwrap();
+
+        let requested_bytes = ranges
+            .iter()
+            .filter_map(|range| range.resolve(11))
+            .map(|range| range.end() - range.start() + 1)
+            .sum::<u64>();
+        assert!(requested_bytes > 11);
+
+        let result = serve_file_with_http_ranges(body, Some(ranges), 11).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].body().len(), 11);
+    }
+
+    #[test]
+    fn too_many_bytes_rejected() {
+        let body = Bytes::from_static(b"hello world");
+        let ranges = "bytes=0-1, 5-9".parse::<HttpRanges>().ok();
+        assert_eq!(
+            serve_file_with_http_ranges(body, ranges, 6).unwrap_err(),
+            ServeRangesError::TooManyBytes {
+                total_bytes: 7,
+                max_total_bytes: 6
+            }
+        );
+    }
+
+    #[test]
+    fn unsatisfiable_ranges_are_dropped() {
+        let body = Bytes::from_static(b"hello world");
+        let ranges = "bytes=0-4, 20-30".parse::<HttpRanges>().ok();
+        let result = serve_file_with_http_ranges(body, ranges, 11).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].body(), &Bytes::from_static(b"hello"));
+        assert_eq!(
+            result[0].header(),
+            Some(HttpContentRange::Bound(
+                Bound::new(0..=4, Some(11)).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn all_unsatisfiable_rejected() {
+        let body = Bytes::from_static(b"hello world");
+        let ranges = "bytes=20-, 30-40".parse::<HttpRanges>().ok();
+        assert!(matches!(
+            serve_file_with_http_ranges(body, ranges, 11),
+            Err(ServeRangesError::Unsatisfiable(_))
+        ));
+    }
+
+    #[test]
+    fn empty_body_with_suffix_returns_empty_response() {
+        let ranges = "bytes=-5, 0-1".parse::<HttpRanges>().ok();
+        let result = serve_file_with_http_ranges(Bytes::new(), ranges, 0).unwrap();
+        assert_eq!(result.len(), 1);
+        assert!(result[0].header().is_none());
+    }
+
+    #[test]
+    fn serving_iter_on_empty_body() {
+        let ranges = "bytes=-5, 0-1".parse::<HttpRanges>().unwrap();
+
+        let mut parts = ranges.into_serving_iter(Bytes::new());
+        let ignored = parts.next().unwrap().unwrap();
+        assert!(ignored.body().is_empty());
+        assert!(ignored.header().is_none());
+        assert!(parts.next().unwrap().is_err());
+        assert!(parts.next().is_none());
+    }
+
+    #[test]
+    fn serving_iter_yields_each_range_in_order() {
+        let body = Bytes::from_static(b"hello world");
+        let ranges = "bytes=6-, 20-30, 0-4".parse::<HttpRanges>().unwrap();
+
+        let mut parts = ranges.into_serving_iter(body);
+        let world = parts.next().unwrap().unwrap();
+        assert_eq!(world.body(), &Bytes::from_static(b"world"));
+        assert_eq!(
+            world.header(),
+            Some(HttpContentRange::Bound(
+                Bound::new(6..=10, Some(11)).unwrap()
+            ))
+        );
+        assert!(parts.next().unwrap().is_err());
+        let hello = parts.next().unwrap().unwrap();
+        assert_eq!(hello.body(), &Bytes::from_static(b"hello"));
+        assert!(parts.next().is_none());
+    }
+}
+
+#[cfg(feature = "http")]
+mod multipart {
+    use bytes::Bytes;
+    use http::HeaderValue;
+
+    use crate::{
+        headers::{
+            HeaderKind, ParseHttpRangeOrContentRangeError,
+            content_range::{Bound, HttpContentRange},
+            ranges::HttpRanges,
+        },
+        multipart::{
+            MultipartByteranges, MultipartError, MultipartRanges, parse_multipart_byteranges,
+        },
+    };
+
+    #[test]
+    fn custom_boundary_produces_deterministic_body() {
+        let mut multipart = MultipartByteranges::new()
+            .with_boundary("THIS_STRING_SEPARATES")
+            .unwrap()
+            .with_content_type(HeaderValue::from_static("text/plain"));
+        multipart.push(
+            Bound::new(0..=4, Some(11)).unwrap(),
+            Bytes::from_static(b"hello"),
+        );
+        multipart.push(
+            Bound::new(6..=10, Some(11)).unwrap(),
+            Bytes::from_static(b"world"),
+        );
+
+        assert_eq!(
+            multipart.multipart_content_type(),
+            "multipart/byteranges; boundary=THIS_STRING_SEPARATES"
+        );
+        assert_eq!(
+            multipart.into_bytes(),
+            Bytes::from_static(
+                b"--THIS_STRING_SEPARATES\r\n\
+                Content-Type: text/plain\r\n\
+                Content-Range: bytes 0-4/11\r\n\
+                \r\n\
+                hello\r\n\
+                --THIS_STRING_SEPARATES\r\n\
+                Content-Type: text/plain\r\n\
+                Content-Range: bytes 6-10/11\r\n\
+                \r\n\
+                world\r\n\
+                --THIS_STRING_SEPARATES--\r\n"
+            )
+        );
+    }
+
+    fn two_parts() -> Bytes {
+        let mut multipart = MultipartByteranges::new()
+            .with_boundary("THIS_STRING_SEPARATES")
+            .unwrap();
+        multipart.push(
+            Bound::new(0..=4, Some(11)).unwrap(),
+            Bytes::from_static(b"hello"),
+        );
+        multipart.push(
+            Bound::new(6..=10, Some(11)).unwrap(),
+            Bytes::from_static(b"world"),
+        );
+        multipart.into_bytes()
+    }
+
+    #[test]
+    fn parse_content_ranges() {
+        let parts = MultipartRanges::parse(&two_parts(), "THIS_STRING_SEPARATES").unwrap();
+        assert_eq!(
+            parts.content_ranges(),
+            [
+                HttpContentRange::Bound(Bound::new(0..=4, Some(11)).unwrap()),
+                HttpContentRange::Bound(Bound::new(6..=10, Some(11)).unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_payloads() {
+        assert_eq!(
+            parse_multipart_byteranges(&two_parts(), "THIS_STRING_SEPARATES").unwrap(),
+            [
+                (
+                    HttpContentRange::Bound(Bound::new(0..=4, Some(11)).unwrap()),
+                    Bytes::from_static(b"hello")
+                ),
+                (
+                    HttpContentRange::Bound(Bound::new(6..=10, Some(11)).unwrap()),
+                    Bytes::from_static(b"world")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_payloads_crlf_edge_cases() {
+        // The CRLF before each delimiter is not part of the payload, but the
+        // ones inside it are kept, and the close delimiter may end the body.
+        let body = b"--b\r\nContent-Range: bytes 0-3/6\r\n\r\n\r\n\r\n\r\n\
+            --b\r\nContent-Range: bytes 4-5/6\r\n\r\nhi\r\n--b--";
+        assert_eq!(
+            parse_multipart_byteranges(body, "b").unwrap(),
+            [
+                (
+                    HttpContentRange::Bound(Bound::new(0..=3, Some(6)).unwrap()),
+                    Bytes::from_static(b"\r\n\r\n")
+                ),
+                (
+                    HttpContentRange::Bound(Bound::new(4..=5, Some(6)).unwrap()),
+                    Bytes::from_static(b"hi")
+                ),
+            ]
+        );
+
+        let unterminated = b"--b\r\nContent-Range: bytes 0-0/1\r\n\r\nx\r\n--b";
+        assert_eq!(
+            parse_multipart_byteranges(unterminated, "b"),
+            Err(MultipartError::MalformedPart)
+        );
+    }
+
+    #[test]
+    fn verify_requested_ranges() {
+        let parts = MultipartRanges::parse(&two_parts(), "THIS_STRING_SEPARATES").unwrap();
+        for requested in ["bytes=0-4, 6-10", "bytes=-5, 0-4", "bytes=0-2, 1-4, 6-"] {
+            let requested = requested.parse::<HttpRanges>().unwrap();
+            assert!(parts.verify(&requested), "{requested:?}");
+        }
+        for requested in ["bytes=0-4", "bytes=0-4, 6-9", "bytes=0-10"] {
+            let requested = requested.parse::<HttpRanges>().unwrap();
+            assert!(!parts.verify(&requested), "{requested:?}");
+        }
+    }
+
+    #[test]
+    fn verify_requires_known_and_consistent_size() {
+        let requested = "bytes=0-4, 6-10".parse::<HttpRanges>().unwrap();
+        for body in [
+            "--b\r\nContent-Range: bytes 0-4/11\r\n\r\nhello\r\n--b\r\nContent-Range: bytes 6-10/*\r\n\r\nworld\r\n--b--",
+            "--b\r\nContent-Range: bytes 0-4/11\r\n\r\nhello\r\n--b\r\nContent-Range: bytes 6-10/12\r\n\r\nworld\r\n--b--",
+            "--b--",
+        ] {
+            let parts = MultipartRanges::parse(body.as_bytes(), "b").unwrap();
+            assert!(!parts.verify(&requested), "{body:?}");
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        for (body, err) in [
+            ("", MultipartError::MissingDelimiter),
+            ("--other\r\n\r\n--other--", MultipartError::MissingDelimiter),
+            (
+                "--b\r\nContent-Range: bytes 0-4/11\r\n\r\nhello",
+                MultipartError::MissingCloseDelimiter,
+            ),
+            (
+                "--b\r\nContent-Type: text/plain\r\n\r\nhello\r\n--b--",
+                MultipartError::MissingContentRange,
+            ),
+            (
+                "--b\r\nContent-Range\r\n\r\nhello\r\n--b--",
+                MultipartError::MalformedPart,
+            ),
+            (
+                "--bContent-Range: bytes 0-4/11\r\n\r\nhello\r\n--b--",
+                MultipartError::MalformedPart,
+            ),
+            (
+                "--b\r\nContent-Range: bytes 0-4\r\n\r\nhello\r\n--b--",
+                MultipartError::InvalidContentRange(
+                    ParseHttpRangeOrContentRangeError::MissingSizeDelimiter(
+                        HeaderKind::ContentRange,
+                    ),
+                ),
+            ),
+        ] {
+            assert_eq!(
+                MultipartRanges::parse(body.as_bytes(), "b"),
+                Err(err),
+                "{body:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn preamble_padding_and_epilogue_ignored() {
+        let body =
+            "preamble\r\n--b  \r\ncontent-range: bytes 0-4/11\r\n\r\nhello\r\n--b--\r\nepilogue";
+        let parts = MultipartRanges::parse(body.as_bytes(), "b").unwrap();
+        assert_eq!(
+            parts.content_ranges(),
+            [HttpContentRange::Bound(
+                Bound::new(0..=4, Some(11)).unwrap()
+            )]
+        );
+    }
+
+    #[test]
+    fn content_length_matches_body() {
+        let mut multipart = MultipartByteranges::new();
+        assert_eq!(multipart.part_count(), 0);
+        assert_eq!(
+            multipart.content_length(),
+            multipart.clone().into_bytes().len() as u64
+        );
+
+        multipart.push(
+            Bound::new(0..=4, Some(11)).unwrap(),
+            Bytes::from_static(b"hello"),
+        );
+        multipart.push(
+            Bound::new(6..=10, None).unwrap(),
+            Bytes::from_static(b"world"),
+        );
+        assert_eq!(multipart.part_count(), 2);
+        assert_eq!(
+            multipart.content_length(),
+            multipart.clone().into_bytes().len() as u64
+        );
+
+        let multipart = multipart.with_content_type(HeaderValue::from_static("text/plain"));
+        assert_eq!(
+            multipart.content_length(),
+            multipart.clone().into_bytes().len() as u64
+        );
+    }
+
+    #[test]
+    fn random_boundaries_differ() {
+        let first = MultipartByteranges::new();
+        let second = MultipartByteranges::new();
+        assert_ne!(first.boundary(), second.boundary());
+    }
+
+    #[test]
+    fn non_token_boundary_is_quoted() {
+        let boundary = "a:b c";
+        let mut multipart = MultipartByteranges::new().with_boundary(boundary).unwrap();
+        multipart.push(
+            Bound::new(0..=4, Some(11)).unwrap(),
+            Bytes::from_static(b"hello"),
+        );
+
+        let content_type = multipart.multipart_content_type();
+        assert_eq!(content_type, "multipart/byteranges; boundary=\"a:b c\"");
+        let parsed_boundary = content_type
+            .to_str()
+            .unwrap()
+            .strip_prefix("multipart/byteranges; boundary=")
+            .and_then(|quoted| quoted.strip_prefix('"'))
+            .and_then(|quoted| quoted.strip_suffix('"'))
+            .unwrap();
+        assert_eq!(parsed_boundary, boundary);
+
+        assert_eq!(
+            parse_multipart_byteranges(&multipart.into_bytes(), parsed_boundary).unwrap(),
+            [(
+                HttpContentRange::Bound(Bound::new(0..=4, Some(11)).unwrap()),
+                Bytes::from_static(b"hello")
+            )]
+        );
+    }
+
+    #[test]
+    fn invalid_boundaries_rejected() {
+        let too_long = "a".repeat(71);
+        for boundary in ["", "trailing ", "semi;colon", "quo\"te", "é", &too_long] {
+            assert!(
+                MultipartByteranges::new().with_boundary(boundary).is_err(),
+                "{boundary:?}"
+            );
+        }
+        assert!(
+            MultipartByteranges::new()
+                .with_boundary("a".repeat(70))
+                .is_ok()
+        );
+    }
+}
+
+#[cfg(test)]
+mod upload {
+    use crate::{UploadDecision, headers::content_range::Bound, upload_check};
+
+    #[test]
+    fn contiguous_chunk_accepted() {
+        let chunk = Bound::new(0..=99, Some(500)).unwrap();
+        assert_eq!(
+            upload_check(chunk, 0, None),
+            UploadDecision::Accept { new_len: 100 }
+        );
+
+        let chunk = Bound::new(100..=199, None).unwrap();
+        assert_eq!(
+            upload_check(chunk, 100, Some(500)),
+            UploadDecision::Accept { new_len: 200 }
+        );
+    }
+
+    #[test]
+    fn gap_and_overlap() {
+        let chunk = Bound::new(200..=299, Some(500)).unwrap();
+        assert_eq!(upload_check(chunk, 100, Some(500)), UploadDecision::Gap);
+        assert_eq!(upload_check(chunk, 250, Some(500)), UploadDecision::Overlap);
+        assert_eq!(upload_check(chunk, 400, Some(500)), UploadDecision::Overlap);
+    }
+
+    #[test]
+    fn size_mismatch() {
+        let chunk = Bound::new(100..=199, Some(400)).unwrap();
+        assert_eq!(
+            upload_check(chunk, 100, Some(500)),
+            UploadDecision::SizeMismatch
+        );
+    }
+
+    #[test]
+    fn unsized_chunk_past_expected_size() {
+        let chunk = Bound::new(400..=500, None).unwrap();
+        assert_eq!(
+            upload_check(chunk, 400, Some(500)),
+            UploadDecision::ExceedsSize
+        );
+
+        let chunk = Bound::new(400..=499, None).unwrap();
+        assert_eq!(
+            upload_check(chunk, 400, Some(500)),
+            UploadDecision::Accept { new_len: 500 }
+        );
+    }
+
+    #[test]
+    fn chunk_ending_at_u64_max() {
+        let chunk = Bound::new(100..=u64::MAX, None).unwrap();
+        assert_eq!(upload_check(chunk, 100, None), UploadDecision::ExceedsSize);
+        assert_eq!(
+            upload_check(chunk, 100, Some(u64::MAX)),
+            UploadDecision::ExceedsSize
+        );
+    }
+}
+
+#[cfg(test)]
+mod resume {
+    use crate::{
+        ResumeDecision,
+        headers::{
+            OrderedRange,
+            content_range::{Bound, HttpContentRange, Unsatisfiable},
+            range::HttpRange,
+        },
+        resume_check,
+    };
+
+    fn delivered(range: std::ops::RangeInclusive<u64>, size: Option<u64>) -> HttpContentRange {
+        HttpContentRange::Bound(Bound::new(range, size).unwrap())
+    }
+
+    #[test]
+    fn contiguous_range_continues() {
+        let last = delivered(0..=99, Some(500));
+        assert_eq!(
+            resume_check(last, HttpRange::StartingPoint(100)),
+            ResumeDecision::Continue
+        );
+        assert_eq!(
+            resume_check(
+                last,
+                HttpRange::Range(OrderedRange::new(100..=199).unwrap())
+            ),
+            ResumeDecision::Continue
+        );
+    }
+
+    #[test]
+    fn contiguous_suffix_continues() {
+        let last = delivered(0..=99, Some(500));
+        assert_eq!(
+            resume_check(last, HttpRange::Suffix(400)),
+            ResumeDecision::Continue
+        );
+    }
+
+    #[test]
+    fn suffix_with_unknown_size_restarts() {
+        let last = delivered(0..=99, None);
+        assert_eq!(
+            resume_check(last, HttpRange::Suffix(400)),
+            ResumeDecision::Restart
+        );
+    }
+
+    #[test]
+    fn range_within_delivered_bytes_overlaps() {
+        let last = delivered(100..=199, Some(500));
+        assert_eq!(
+            resume_check(last, HttpRange::StartingPoint(150)),
+            ResumeDecision::Overlap
+        );
+        assert_eq!(
+            resume_check(last, HttpRange::StartingPoint(100)),
+            ResumeDecision::Overlap
+        );
+    }
+
+    #[test]
+    fn range_from_start_restarts() {
+        let last = delivered(0..=99, Some(500));
+        assert_eq!(
+            resume_check(last, HttpRange::StartingPoint(0)),
+            ResumeDecision::Restart
+        );
+    }
+
+    #[test]
+    fn range_with_gap_restarts() {
+        let last = delivered(0..=99, Some(500));
+        assert_eq!(
+            resume_check(last, HttpRange::StartingPoint(200)),
+            ResumeDecision::Restart
+        );
+    }
+
+    #[test]
+    fn unsatisfiable_last_response_restarts() {
+        let last = HttpContentRange::Unsatisfiable(Unsatisfiable::new(500));
+        assert_eq!(
+            resume_check(last, HttpRange::StartingPoint(100)),
+            ResumeDecision::Restart
+        );
+    }
+}
+
+#[cfg(feature = "axum")]
+mod axum_range_extractor {
+    use std::{
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    use axum_core::extract::OptionalFromRequestParts;
+    use http::{Method, Request, header::RANGE};
+
+    use crate::headers::{
+        HeaderKind, OrderedRange, ParseHttpRangeOrContentRangeError, range::HttpRange,
+    };
+
+    fn extract(method: Method, range: Option<&str>) -> Option<HttpRange> {
+        extract_all(method, range.as_slice()).unwrap()
+    }
+
+    fn extract_all(
+        method: Method,
+        ranges: &[&str],
+    ) -> Result<Option<HttpRange>, ParseHttpRangeOrContentRangeError> {
+        let mut builder = Request::builder().method(method);
+        for range in ranges {
+            builder = builder.header(RANGE, *range);
+        }
+        let (mut parts, ()) = builder.body(()).unwrap().into_parts();
+
+        let fut =
+            pin!(<HttpRange as OptionalFromRequestParts<()>>::from_request_parts(&mut parts, &()));
+        match fut.poll(&mut Context::from_waker(Waker::noop())) {
+            Poll::Ready(result) => result,
+            Poll::Pending => unreachable!("the extractor never awaits"),
+        }
+    }
+
+    #[test]
+    fn get_request_extracts_range() {
+        assert_eq!(
+            extract(Method::GET, Some("bytes=0-10")),
+            Some(HttpRange::Range(OrderedRange::new(0..=10).unwrap()))
+        );
+    }
+
+    #[test]
+    fn non_get_request_ignores_range() {
+        assert_eq!(extract(Method::POST, Some("bytes=0-10")), None);
+        assert_eq!(extract(Method::PUT, Some("bytes=0-10")), None);
+    }
+
+    #[test]
+    fn missing_range_extracts_none() {
+        assert_eq!(extract(Method::GET, None), None);
+    }
+
+    #[test]
+    fn malformed_range_is_ignored() {
+        assert_eq!(extract(Method::GET, Some("bytes=10-0")), None);
+    }
+
+    #[test]
+    fn multiple_range_headers_are_rejected() {
+        assert_eq!(
+            extract_all(Method::GET, &["bytes=0-10", "bytes=20-30"]),
+            Err(ParseHttpRangeOrContentRangeError::MultipleHeaders(
+                HeaderKind::Range
+            ))
+        );
+        assert_eq!(
+            extract_all(Method::POST, &["bytes=0-10", "bytes=20-30"]),
+            Ok(None)
+        );
+    }
+}
+
+#[cfg(feature = "futures-io")]
+mod range_body {
+    use std::{
+        io::{self, SeekFrom},
+        pin::{Pin, pin},
+        task::{Context, Poll, Waker},
+    };
+
+    use futures_io::{AsyncRead, AsyncSeek};
+    use http_body::Body;
+
+    use crate::{RangeBody, file_range, headers::range::HttpRange};
+
+    /// A source of `len` bytes, where each byte is its offset truncated to `u8`.
+    ///
+    /// The bytes are computed on the fly, so it can be larger than the memory.
+    struct Cursor {
+        len: u64,
+        position: u64,
     }
 
-    #[test]
-    fn starting_point_beyond_size() {
-        let result = file_range(10, Some(HttpRange::StartingPoint(20)));
-        assert!(result.is_err());
+    impl AsyncRead for Cursor {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            let mut read = 0;
+            for byte in buf.iter_mut() {
+                if self.position >= self.len {
+                    break;
+                }
+                *byte = self.position as u8;
+                self.position += 1;
+                read += 1;
+            }
+            Poll::Ready(Ok(read))
+        }
     }
 
-    #[test]
-    fn range_single_byte() {
-        let range = HttpRange::Range(OrderedRange::new(0..=0).unwrap());
-        let result = file_range(10, Some(range)).unwrap();
-        assert_eq!(result.range(), &(0..1));
+    impl AsyncSeek for Cursor {
+        fn poll_seek(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            pos: SeekFrom,
+        ) -> Poll<io::Result<u64>> {
+            let position = seek(self.position, self.len, pos)?;
+            self.position = position;
+            Poll::Ready(Ok(position))
+        }
     }
 
-    #[test]
-    fn range_full_file() {
-        let range = HttpRange::Range(OrderedRange::new(0..=9).unwrap());
-        let result = file_range(10, Some(range)).unwrap();
-        assert_eq!(result.range(), &(0..10));
+    /// Resolves `pos` like [`std::io::Cursor`], for a source of `len` bytes
+    /// currently at `position`.
+    fn seek(position: u64, len: u64, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(position) => return Ok(position),
+            SeekFrom::End(offset) => (len, offset),
+            SeekFrom::Current(offset) => (position, offset),
+        };
+        base.checked_add_signed(offset).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })
     }
 
-    #[test]
-    fn range_end_at_size_is_clamped() {
-        let range = HttpRange::Range(OrderedRange::new(0..=10).unwrap());
-        let result = file_range(10, Some(range)).unwrap();
-        assert_eq!(result.range(), &(0..10));
+    fn collect(body: RangeBody<Cursor>) -> io::Result<Vec<u8>> {
+        let mut body = pin!(body);
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut collected = Vec::new();
+        loop {
+            match body.as_mut().poll_frame(&mut cx) {
+                Poll::Ready(Some(frame)) => {
+                    collected.extend_from_slice(&frame?.into_data().unwrap())
+                }
+                Poll::Ready(None) => return Ok(collected),
+                Poll::Pending => unreachable!("the cursor never blocks"),
+            }
+        }
     }
 
-    #[test]
-    fn range_beyond_size_is_clamped() {
-        let range = HttpRange::Range(OrderedRange::new(0..=50).unwrap());
-        let result = file_range(10, Some(range)).unwrap();
-        assert_eq!(result.range(), &(0..10));
+    fn cursor(len: u64) -> Cursor {
+        Cursor { len, position: 0 }
     }
 
     #[test]
-    fn range_end_at_u64_max_is_clamped() {
-        let range = HttpRange::Range(OrderedRange::new(0..=u64::MAX).unwrap());
-        let result = file_range(10, Some(range)).unwrap();
-        assert_eq!(result.range(), &(0..10));
+    fn serves_the_range() {
+        let content_range = file_range(100, Some(HttpRange::Suffix(10))).unwrap();
+        let body = RangeBody::new(cursor(100), content_range.range().clone());
+        assert_eq!(body.size_hint().exact(), Some(10));
+        assert_eq!(collect(body).unwrap(), (90..100).collect::<Vec<u8>>());
     }
 
     #[test]
-    fn range_start_at_size_is_unsatisfiable() {
-        let range = HttpRange::Range(OrderedRange::new(10..=20).unwrap());
-        let result = file_range(10, Some(range));
-        assert!(result.is_err());
+    fn serves_ranges_larger_than_a_chunk() {
+        let body = RangeBody::new(cursor(20_000), 1..19_999);
+        let expected = (1..19_999).map(|i| i as u8).collect::<Vec<_>>();
+        assert_eq!(collect(body).unwrap(), expected);
     }
 
     #[test]
-    fn range_start_beyond_size_is_unsatisfiable() {
-        let range = HttpRange::Range(OrderedRange::new(50..=100).unwrap());
-        let result = file_range(10, Some(range));
-        assert!(result.is_err());
+    fn empty_range_ends_immediately() {
+        let body = RangeBody::new(cursor(100), 10..10);
+        assert!(body.is_end_stream());
+        assert_eq!(body.size_hint().exact(), Some(0));
+        assert!(collect(body).unwrap().is_empty());
     }
 
     #[test]
-    fn suffix_entire_file() {
-        let result = file_range(10, Some(HttpRange::Suffix(10))).unwrap();
-        assert_eq!(result.range(), &(0..10));
+    fn serves_offsets_beyond_u32() {
+        let len = 3 * u64::from(u32::MAX);
+        let start = u64::from(u32::MAX) - 10;
+        let end = u64::from(u32::MAX) + 10;
+
+        let body = RangeBody::new(cursor(len), start..end);
+        let mut body = pin!(body);
+        let mut cx = Context::from_waker(Waker::noop());
+        let Poll::Ready(Some(Ok(frame))) = body.as_mut().poll_frame(&mut cx) else {
+            panic!("expected a data frame");
+        };
+        let expected = (start..end).map(|i| i as u8).collect::<Vec<_>>();
+        assert_eq!(frame.into_data().unwrap(), expected);
+        assert!(body.is_end_stream());
     }
 
     #[test]
-    fn suffix_last_byte() {
-        let result = file_range(10, Some(HttpRange::Suffix(1))).unwrap();
-        assert_eq!(result.range(), &(9..10));
+    fn ranges_longer_than_u32_are_read_in_chunks() {
+        let len = 3 * u64::from(u32::MAX);
+        let range = 1..len - 1;
+
+        let body = RangeBody::new(cursor(len), range.clone());
+        assert_eq!(body.size_hint().exact(), Some(range.end - range.start));
+
+        let mut body = pin!(body);
+        let mut cx = Context::from_waker(Waker::noop());
+        let Poll::Ready(Some(Ok(frame))) = body.as_mut().poll_frame(&mut cx) else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(frame.into_data().unwrap().len(), 8 * 1024);
+        assert_eq!(
+            body.size_hint().exact(),
+            Some(range.end - range.start - 8 * 1024)
+        );
     }
 
+    /// On 32-bit targets the offsets and the length of the range do not fit
+    /// `usize`, which is what the two tests above are about.
+    #[cfg(target_pointer_width = "32")]
     #[test]
-    fn suffix_middle() {
-        let result = file_range(10, Some(HttpRange::Suffix(5))).unwrap();
-        assert_eq!(result.range(), &(5..10));
+    fn offsets_beyond_usize() {
+        let len = 3 * u64::from(u32::MAX);
+        let range = u64::from(u32::MAX) + 1..len - 1;
+        assert!(usize::try_from(range.start).is_err());
+        assert!(usize::try_from(range.end - range.start).is_err());
+
+        let body = RangeBody::new(cursor(len), range.clone());
+        let mut body = pin!(body);
+        let mut cx = Context::from_waker(Waker::noop());
+        let Poll::Ready(Some(Ok(frame))) = body.as_mut().poll_frame(&mut cx) else {
+            panic!("expected a data frame");
+        };
+        let expected = (range.start..range.start + 8 * 1024)
+            .map(|i| i as u8)
+            .collect::<Vec<_>>();
+        assert_eq!(frame.into_data().unwrap(), expected);
     }
 
     #[test]
-    fn suffix_zero_is_unsatisfiable() {
-        let result = file_range(10, Some(HttpRange::Suffix(0)));
-        assert!(result.is_err());
+    fn cursor_seeks_from_every_origin() {
+        assert_eq!(seek(5, 100, SeekFrom::Start(10)).unwrap(), 10);
+        assert_eq!(seek(5, 100, SeekFrom::End(-10)).unwrap(), 90);
+        assert_eq!(seek(5, 100, SeekFrom::Current(3)).unwrap(), 8);
+        assert_eq!(
+            seek(5, 100, SeekFrom::Current(-6)).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
     }
 
     #[test]
-    fn suffix_exceeds_size_is_clamped() {
-        let result = file_range(10, Some(HttpRange::Suffix(11))).unwrap();
-        assert_eq!(result.range(), &(0..10));
+    fn truncated_source_errors() {
+        let body = RangeBody::new(cursor(50), 40..60);
+        assert_eq!(
+            collect(body).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
     }
+}
+
+#[cfg(feature = "axum")]
+mod axum_response {
+    use axum_core::response::IntoResponse;
+    use bytes::Bytes;
+    use http::{
+        HeaderValue, StatusCode,
+        header::{ACCEPT_RANGES, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE, VARY},
+    };
+
+    use crate::{
+        BodyRange, RangeResponse, RangeResponseKind,
+        headers::{
+            accept_ranges::AcceptRanges,
+            content_range::{Bound, HttpContentRange},
+            range::HttpRange,
+        },
+        multipart::MultipartByteranges,
+        serve_file_with_accept_ranges, serve_file_with_http_range, serve_precompressed,
+    };
 
     #[test]
-    fn size_one_no_range() {
-        let result = file_range(1, None).unwrap();
-        assert_eq!(result.range(), &(0..1));
+    fn partial_response_has_accept_ranges() {
+        let body = Bytes::from_static(b"hello world");
+        let response = serve_file_with_http_range(body, Some(HttpRange::Suffix(5)))
+            .unwrap()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[ACCEPT_RANGES], "bytes");
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes 6-10/11");
     }
 
     #[test]
-    fn size_one_starting_point_zero() {
-        let result = file_range(1, Some(HttpRange::StartingPoint(0))).unwrap();
-        assert_eq!(result.range(), &(0..1));
+    fn responses_have_content_length() {
+        let body = Bytes::from_static(b"hello world");
+        let response = serve_file_with_http_range(body.clone(), Some(HttpRange::Suffix(5)))
+            .unwrap()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[CONTENT_LENGTH], "5");
+        assert_eq!(body_len(response), 5);
+
+        let response = serve_file_with_http_range(body, None)
+            .unwrap()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_LENGTH], "11");
     }
 
     #[test]
-    fn size_one_suffix_one() {
-        let result = file_range(1, Some(HttpRange::Suffix(1))).unwrap();
-        assert_eq!(result.range(), &(0..1));
+    fn precompressed_response_has_content_encoding() {
+        let body = Bytes::from_static(b"compressed");
+        let response = serve_precompressed(
+            body,
+            HeaderValue::from_static("gzip"),
+            Some(HttpRange::StartingPoint(4)),
+        )
+        .unwrap()
+        .into_response();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes 4-9/10");
+        assert_eq!(response.headers()[CONTENT_LENGTH], "6");
     }
 
     #[test]
-    fn content_range_header_present_for_range_request() {
-        let result = file_range(10, Some(HttpRange::StartingPoint(0))).unwrap();
-        let header = result.header().unwrap();
-        assert_eq!(
-            header,
-            HttpContentRange::Bound(Bound::new(0..=9, Some(10)).unwrap())
+    fn multipart_response_has_content_length() {
+        let mut multipart = MultipartByteranges::new()
+            .with_boundary("BOUNDARY")
+            .unwrap();
+        multipart.push(
+            Bound::new(0..=4, Some(11)).unwrap(),
+            Bytes::from_static(b"hello"),
         );
-    }
+        multipart.push(
+            Bound::new(6..=10, Some(11)).unwrap(),
+            Bytes::from_static(b"world"),
+        );
+        let content_length = multipart.content_length();
 
-    #[test]
-    fn size_zero_no_range() {
-        let result = file_range(0, None).unwrap();
-        assert!(result.header().is_none());
-        assert_eq!(result.range(), &(0..0));
+        let response = multipart.into_response();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            response.headers()[CONTENT_LENGTH],
+            content_length.to_string()
+        );
+        assert_eq!(body_len(response), usize::try_from(content_length).unwrap());
     }
 
     #[test]
-    fn size_zero_starting_point_is_unsatisfiable() {
-        let result = file_range(0, Some(HttpRange::StartingPoint(0)));
-        assert!(result.is_err());
+    fn unknown_size_bound_response() {
+        let header = HttpContentRange::Bound(Bound::new(0..=9, None).unwrap());
+        let response =
+            BodyRange::new(Bytes::from_static(b"0123456789"), Some(header)).into_response();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes 0-9/*");
     }
 
     #[test]
-    fn size_zero_range_is_unsatisfiable() {
-        let range = HttpRange::Range(OrderedRange::new(0..=0).unwrap());
-        let result = file_range(0, Some(range));
-        assert!(result.is_err());
+    fn full_response_has_accept_ranges() {
+        let body = Bytes::from_static(b"hello world");
+        let response = serve_file_with_http_range(body, None)
+            .unwrap()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[ACCEPT_RANGES], "bytes");
+        assert!(!response.headers().contains_key(CONTENT_RANGE));
     }
 
     #[test]
-    fn size_zero_suffix_serves_full_response() {
-        let result = file_range(0, Some(HttpRange::Suffix(1))).unwrap();
-        assert!(result.header().is_none());
-        assert_eq!(result.range(), &(0..0));
+    fn responses_vary_on_range() {
+        let body = Bytes::from_static(b"hello world");
+        let response = serve_file_with_http_range(body.clone(), Some(HttpRange::Suffix(5)))
+            .unwrap()
+            .into_response();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[VARY], "Range");
+
+        let response = serve_file_with_http_range(body, None)
+            .unwrap()
+            .into_response();
+        assert_eq!(response.headers()[VARY], "Range");
     }
 
     #[test]
-    fn size_zero_suffix_zero_is_unsatisfiable() {
-        let result = file_range(0, Some(HttpRange::Suffix(0)));
-        assert!(result.is_err());
+    fn vary_range_opt_out() {
+        let body = Bytes::from_static(b"hello world");
+        let response = serve_file_with_http_range(body.clone(), Some(HttpRange::Suffix(5)))
+            .unwrap()
+            .with_vary_range(false)
+            .into_response();
+        assert!(!response.headers().contains_key(VARY));
+
+        let response =
+            serve_file_with_accept_ranges(body, Some(HttpRange::Suffix(5)), AcceptRanges::None)
+                .unwrap()
+                .into_response();
+        assert!(!response.headers().contains_key(VARY));
     }
-}
 
-#[cfg(test)]
-mod serve_file {
-    use bytes::Bytes;
+    fn body_len(response: axum_core::response::Response) -> usize {
+        use http_body::Body;
 
-    use crate::{headers::range::HttpRange, serve_file_with_http_range};
+        let size_hint = response.into_body().size_hint();
+        usize::try_from(size_hint.exact().unwrap()).unwrap()
+    }
 
     #[test]
-    fn no_range_returns_full_body() {
+    fn head_partial_response() {
         let body = Bytes::from_static(b"hello world");
-        let result = serve_file_with_http_range(body.clone(), None).unwrap();
-        assert_eq!(result.body(), &body);
-        assert!(result.header().is_none());
+        let response = serve_file_with_http_range(body, Some(HttpRange::Suffix(5)))
+            .unwrap()
+            .into_head_response();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes 6-10/11");
+        assert_eq!(response.headers()[CONTENT_LENGTH], "5");
+        assert_eq!(body_len(response), 0);
     }
 
     #[test]
-    fn range_slices_body() {
+    fn head_full_response() {
         let body = Bytes::from_static(b"hello world");
-        let range = Some(HttpRange::StartingPoint(6));
-        let result = serve_file_with_http_range(body, range).unwrap();
-        assert_eq!(result.body(), &Bytes::from_static(b"world"));
-        assert!(result.header().is_some());
+        let response = serve_file_with_http_range(body, None)
+            .unwrap()
+            .into_head_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_LENGTH], "11");
+        assert_eq!(body_len(response), 0);
     }
 
     #[test]
-    fn suffix_slices_from_end() {
+    fn head_unsatisfiable_response() {
         let body = Bytes::from_static(b"hello world");
-        let range = Some(HttpRange::Suffix(5));
-        let result = serve_file_with_http_range(body, range).unwrap();
-        assert_eq!(result.body(), &Bytes::from_static(b"world"));
+        let response = serve_file_with_http_range(body, Some(HttpRange::StartingPoint(20)))
+            .unwrap_err()
+            .into_head_response();
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes */11");
+        assert_eq!(body_len(response), 0);
     }
 
     #[test]
-    fn empty_body_returns_empty_response() {
-        let body = Bytes::new();
-        let result = serve_file_with_http_range(body, None).unwrap();
-        assert!(result.body().is_empty());
-        assert!(result.header().is_none());
+    fn unsupported_ranges_are_ignored() {
+        let body = Bytes::from_static(b"hello world");
+        let body_range =
+            serve_file_with_accept_ranges(body, Some(HttpRange::Suffix(5)), AcceptRanges::None)
+                .unwrap();
+        assert_eq!(body_range.accept_ranges(), AcceptRanges::None);
+
+        let response = body_range.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[ACCEPT_RANGES], "none");
     }
 
     #[test]
-    fn suffix_zero_is_unsatisfiable() {
-        let body = Bytes::from_static(b"hello");
-        let result = serve_file_with_http_range(body, Some(HttpRange::Suffix(0)));
-        assert!(result.is_err());
+    fn range_response_from_partial_body_range() {
+        let body = Bytes::from_static(b"hello world");
+        let range_response =
+            RangeResponse::from(serve_file_with_http_range(body, Some(HttpRange::Suffix(5))));
+        assert_eq!(range_response.kind(), RangeResponseKind::Partial);
+        assert_eq!(range_response.body(), Some(&Bytes::from_static(b"world")));
+
+        let response = range_response.into_response();
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes 6-10/11");
+        assert_eq!(response.headers()[ACCEPT_RANGES], "bytes");
+        assert_eq!(response.headers()[VARY], "Range");
+        assert_eq!(body_len(response), 5);
     }
 
     #[test]
-    fn empty_body_with_suffix_returns_empty_response() {
-        let body = Bytes::new();
-        let result = serve_file_with_http_range(body, Some(HttpRange::Suffix(5))).unwrap();
-        assert!(result.body().is_empty());
-        assert!(result.header().is_none());
-    }
-}
-
-#[cfg(feature = "axum")]
-mod axum_range_extractor {
-    use std::{
-        pin::pin,
-        task::{Context, Poll, Waker},
-    };
-
-    use axum_core::extract::OptionalFromRequestParts;
-    use http::{Method, Request, header::RANGE};
-
-    use crate::headers::{OrderedRange, range::HttpRange};
-
-    fn extract(method: Method, range: Option<&str>) -> Option<HttpRange> {
-        let mut builder = Request::builder().method(method);
-        if let Some(range) = range {
-            builder = builder.header(RANGE, range);
-        }
-        let (mut parts, ()) = builder.body(()).unwrap().into_parts();
-
-        let fut =
-            pin!(<HttpRange as OptionalFromRequestParts<()>>::from_request_parts(&mut parts, &()));
-        match fut.poll(&mut Context::from_waker(Waker::noop())) {
-            Poll::Ready(Ok(range)) => range,
-            Poll::Ready(Err(infallible)) => match infallible {},
-            Poll::Pending => unreachable!("the extractor never awaits"),
-        }
+    fn range_response_full_advertises_accept_ranges() {
+        let body = Bytes::from_static(b"hello world");
+        let range_response = RangeResponse::from(serve_file_with_accept_ranges(
+            body,
+            Some(HttpRange::Suffix(5)),
+            AcceptRanges::None,
+        ))
+        .with_accept_ranges(AcceptRanges::Bytes);
+        assert_eq!(range_response.kind(), RangeResponseKind::Full);
+        assert_eq!(range_response.content_range(), None);
+
+        let response = range_response.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(CONTENT_RANGE));
+        assert_eq!(response.headers()[ACCEPT_RANGES], "bytes");
+        assert_eq!(body_len(response), 11);
     }
 
     #[test]
-    fn get_request_extracts_range() {
-        assert_eq!(
-            extract(Method::GET, Some("bytes=0-10")),
-            Some(HttpRange::Range(OrderedRange::new(0..=10).unwrap()))
-        );
+    fn range_response_unsatisfiable() {
+        let body = Bytes::from_static(b"hello world");
+        let range_response = RangeResponse::from(serve_file_with_http_range(
+            body,
+            Some(HttpRange::StartingPoint(20)),
+        ));
+        assert_eq!(range_response.kind(), RangeResponseKind::Unsatisfiable);
+        assert_eq!(range_response.body(), None);
+
+        let response = range_response.with_vary_range(false).into_response();
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+        assert_eq!(response.headers()[CONTENT_RANGE], "bytes */11");
+        assert_eq!(response.headers()[ACCEPT_RANGES], "bytes");
+        assert!(!response.headers().contains_key(VARY));
+        assert_eq!(body_len(response), 0);
     }
 
     #[test]
-    fn non_get_request_ignores_range() {
-        assert_eq!(extract(Method::POST, Some("bytes=0-10")), None);
-        assert_eq!(extract(Method::PUT, Some("bytes=0-10")), None);
+    #[cfg(not(feature = "verbose-errors"))]
+    fn parse_error_response_has_empty_body() {
+        let error = "bytes=10".parse::<HttpRange>().unwrap_err();
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(body_len(response), 0);
     }
 
     #[test]
-    fn missing_range_extracts_none() {
-        assert_eq!(extract(Method::GET, None), None);
-    }
+    #[cfg(feature = "verbose-errors")]
+    fn parse_error_response_has_message() {
+        use std::{
+            pin::pin,
+            task::{Context, Poll, Waker},
+        };
 
-    #[test]
-    fn malformed_range_is_ignored() {
-        assert_eq!(extract(Method::GET, Some("bytes=10-0")), None);
+        use http_body::Body;
+
+        let error = "bytes=10".parse::<HttpRange>().unwrap_err();
+        let message = error.to_string();
+        let response = error.into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.headers()[http::header::CONTENT_TYPE],
+            "text/plain; charset=utf-8"
+        );
+
+        let mut body = pin!(response.into_body());
+        let Poll::Ready(Some(Ok(frame))) = body
+            .as_mut()
+            .poll_frame(&mut Context::from_waker(Waker::noop()))
+        else {
+            panic!("expected a data frame");
+        };
+        assert_eq!(frame.into_data().unwrap(), message.as_bytes());
     }
 }
 
-#[cfg(test)]
+#[cfg(feature = "http")]
 mod if_range {
     use http::HeaderValue;
 
@@ -878,3 +4088,146 @@ mod if_range {
         assert_eq!(ir.evaluate(range, Some(&lm), None), None);
     }
 }
+
+#[cfg(test)]
+mod etag {
+    #[cfg(feature = "http")]
+    use http::HeaderValue;
+
+    use crate::headers::etag::{ETag, InvalidETag};
+
+    #[test]
+    fn parse_strong() {
+        let etag = "\"abc123\"".parse::<ETag>().unwrap();
+        assert!(!etag.is_weak());
+        assert_eq!(etag.opaque_tag(), "abc123");
+        assert_eq!(etag.to_string(), "\"abc123\"");
+    }
+
+    #[test]
+    fn parse_weak() {
+        let etag = "W/\"abc123\"".parse::<ETag>().unwrap();
+        assert!(etag.is_weak());
+        assert_eq!(etag.opaque_tag(), "abc123");
+    }
+
+    #[test]
+    #[cfg(feature = "http")]
+    fn header_value_conversion() {
+        let etag = "W/\"abc123\"".parse::<ETag>().unwrap();
+        assert_eq!(HeaderValue::from(&etag), "W/\"abc123\"");
+        assert_eq!(ETag::try_from(HeaderValue::from(&etag)), Ok(etag));
+    }
+
+    #[test]
+    fn parse_empty_opaque_tag() {
+        assert_eq!("\"\"".parse::<ETag>().unwrap().opaque_tag(), "");
+    }
+
+    #[test]
+    fn invalid_rejected() {
+        for value in [
+            "",
+            "abc",
+            "\"abc",
+            "abc\"",
+            "\"",
+            "w/\"abc\"",
+            "W/abc",
+            "\"a\"b\"",
+            "\"a b\"",
+        ] {
+            assert_eq!(value.parse::<ETag>(), Err(InvalidETag), "{value:?}");
+        }
+    }
+
+    #[test]
+    fn strong_comparison() {
+        let strong = "\"1\"".parse::<ETag>().unwrap();
+        let weak = "W/\"1\"".parse::<ETag>().unwrap();
+        let other = "\"2\"".parse::<ETag>().unwrap();
+
+        assert!(strong.strong_eq(&strong));
+        assert!(!strong.strong_eq(&weak));
+        assert!(!weak.strong_eq(&strong));
+        assert!(!weak.strong_eq(&weak));
+        assert!(!strong.strong_eq(&other));
+    }
+
+    #[test]
+    fn weak_comparison() {
+        let strong = "\"1\"".parse::<ETag>().unwrap();
+        let weak = "W/\"1\"".parse::<ETag>().unwrap();
+        let other = "W/\"2\"".parse::<ETag>().unwrap();
+
+        assert!(strong.weak_eq(&strong));
+        assert!(strong.weak_eq(&weak));
+        assert!(weak.weak_eq(&strong));
+        assert!(weak.weak_eq(&weak));
+        assert!(!weak.weak_eq(&other));
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use crate::headers::{
+        OrderedRange,
+        content_range::{Bound, HttpContentRange},
+        range::HttpRange,
+    };
+
+    fn entropy() -> Vec<u8> {
+        // A simple xorshift generator keeps the test deterministic.
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state.to_le_bytes()[0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn generated_ordered_ranges_are_ordered() {
+        let data = entropy();
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let range = OrderedRange::arbitrary(&mut u).unwrap();
+            assert!(OrderedRange::new(range.start()..=range.end()).is_ok());
+        }
+    }
+
+    #[test]
+    fn generated_bounds_respect_size() {
+        let data = entropy();
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let bound = Bound::arbitrary(&mut u).unwrap();
+            let range = bound.range();
+            assert_eq!(
+                Bound::new(range.start()..=range.end(), bound.size()),
+                Ok(bound)
+            );
+        }
+    }
+
+    #[test]
+    fn generated_headers_round_trip() {
+        let data = entropy();
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let range = HttpRange::arbitrary(&mut u).unwrap();
+            assert_eq!(range.to_string().parse::<HttpRange>(), Ok(range));
+
+            let content_range = HttpContentRange::arbitrary(&mut u).unwrap();
+            assert_eq!(
+                content_range.to_string().parse::<HttpContentRange>(),
+                Ok(content_range)
+            );
+        }
+    }
+}
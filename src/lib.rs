@@ -1,39 +1,402 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
-use std::ops::Range;
+use std::{cmp::Ordering, ops::Range};
 
 use bytes::Bytes;
+#[cfg(feature = "http")]
+use http::HeaderValue;
 
 pub mod headers;
+#[cfg(feature = "http")]
+pub mod multipart;
+#[cfg(feature = "futures-io")]
+mod range_body;
+mod sliceable;
+
+#[cfg(feature = "futures-io")]
+pub use crate::range_body::RangeBody;
+pub use crate::sliceable::{ArcSlice, Sliceable};
 
 use crate::headers::{
+    DEFAULT_MAX_HEADER_LEN, InvalidOrderedRange, OrderedRange,
+    accept_ranges::AcceptRanges,
     content_range::{Bound, HttpContentRange, Unsatisfiable},
     range::HttpRange,
+    ranges::HttpRanges,
 };
 
-/// Returns a [`BodyRange`] of [`Bytes`] if the provided [`HttpRange`] is satisfiable, otherwise it returns [`UnsatisfiableRange`].
+/// The `bytes` range unit, the only one defined by [RFC 9110 Section 14.1].
+///
+/// Range unit names are case-insensitive, so compare against it with
+/// [`str::eq_ignore_ascii_case`].
+///
+/// [RFC 9110 Section 14.1]: https://www.rfc-editor.org/rfc/rfc9110#section-14.1
+pub const BYTES_UNIT: &str = "bytes";
+
+/// Returns a [`BodyRange`] of the sliced body if the provided [`HttpRange`] is satisfiable, otherwise it returns [`UnsatisfiableRange`].
+///
+/// The body is sliced without copying: [`Bytes`] are served as [`Bytes`], while
+/// `Arc<[u8]>` is served as an [`ArcSlice`], see [`Sliceable`].
+///
+/// If the `tracing` feature is enabled, the requested range and the outcome are
+/// logged as `debug` events.
 ///
 /// [`HttpRange`]: crate::headers::range::HttpRange
-pub fn serve_file_with_http_range(
-    body: Bytes,
+pub fn serve_file_with_http_range<T: Sliceable>(
+    body: T,
     http_range: Option<HttpRange>,
-) -> Result<BodyRange<Bytes>, UnsatisfiableRange> {
+) -> Result<BodyRange<T::Slice>, UnsatisfiableRange> {
     let size = u64::try_from(body.len()).expect("we do not support 128bit usize");
+    let content_range = traced_file_range(size, http_range, 0)?;
 
-    let content_range = file_range(size, http_range)?;
+    Ok(slice_body(body, content_range))
+}
+
+/// Resolves the range like [`file_range_with_policy`], logging the requested range
+/// and the outcome as `debug` events if the `tracing` feature is enabled, so that
+/// every serving function traces the same way.
+fn traced_file_range(
+    size: u64,
+    http_range: Option<HttpRange>,
+    min_range_size: u64,
+) -> Result<ContentRange, UnsatisfiableRange> {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(size, range = ?http_range, "serving range request");
+
+    let content_range = file_range_with_policy(size, http_range, min_range_size);
+
+    #[cfg(feature = "tracing")]
+    match &content_range {
+        Ok(ContentRange {
+            header: Some(header),
+            ..
+        }) => tracing::debug!(%header, "serving partial content"),
+        Ok(ContentRange { header: None, .. }) => tracing::debug!("serving full content"),
+        Err(UnsatisfiableRange(header)) => tracing::debug!(%header, "range not satisfiable"),
+    }
 
+    content_range
+}
+
+/// Like [`serve_file_with_http_range`], but applies the limits of the provided [`RangeConfig`].
+///
+/// The range is ignored below [`RangeConfig::min_range_size`], as in
+/// [`file_range_with_policy`]. If the `tracing` feature is enabled, the same
+/// `debug` events are logged, and a range that is tiny relative to the
+/// representation, see [`RangeConfig::is_tiny_range`], is also logged as a `warn`
+/// event, since many of them may be a sign of range amplification probing.
+///
+/// [`HttpRange`]: crate::headers::range::HttpRange
+pub fn serve_file_with_config<T: Sliceable>(
+    body: T,
+    http_range: Option<HttpRange>,
+    config: &RangeConfig,
+) -> Result<BodyRange<T::Slice>, UnsatisfiableRange> {
+    let size = u64::try_from(body.len()).expect("we do not support 128bit usize");
+    let content_range = traced_file_range(size, http_range, config.min_range_size)?;
+
+    #[cfg(feature = "tracing")]
+    if content_range.header.is_some() {
+        let length = content_range.range.end - content_range.range.start;
+        if config.is_tiny_range(length, size) {
+            tracing::warn!(
+                size,
+                length,
+                tiny_range_ratio = config.tiny_range_ratio,
+                "tiny range of a large representation requested"
+            );
+        }
+    }
+
+    Ok(slice_body(body, content_range))
+}
+
+fn slice_body<T: Sliceable>(body: T, content_range: ContentRange) -> BodyRange<T::Slice> {
     let start = usize::try_from(content_range.range.start).expect("u64 doesn't fit usize");
     let end = usize::try_from(content_range.range.end).expect("u64 doesn't fit usize");
 
-    Ok(BodyRange {
-        body: body.slice(start..end),
-        header: content_range.header,
-    })
+    BodyRange::new(body.slice(start..end), content_range.header)
+}
+
+/// Like [`serve_file_with_http_range`], but serves the `window` of `body` as if it was
+/// the whole representation.
+///
+/// This is useful when many files are stored in a single blob. The provided [`HttpRange`]
+/// is resolved relative to the `window`, and the size of the `Content-Range` is the
+/// length of the `window`, so the returned body never extends beyond it.
+///
+/// # Panics
+///
+/// Panics if `window` is out of the bounds of `body`, like [`Bytes::slice`].
+///
+/// [`HttpRange`]: crate::headers::range::HttpRange
+pub fn serve_file_with_http_range_windowed(
+    body: Bytes,
+    window: Range<usize>,
+    http_range: Option<HttpRange>,
+) -> Result<BodyRange<Bytes>, UnsatisfiableRange> {
+    serve_file_with_http_range(Bytes::slice(&body, window), http_range)
+}
+
+/// Like [`serve_file_with_http_range`], but also returns the [`RangeResponseKind`] of
+/// the response, for instance to count the `200`, `206` and `416` responses.
+///
+/// The kind is [`RangeResponseKind::Unsatisfiable`] if and only if the result is an error.
+pub fn serve_file_with_response_kind<T: Sliceable>(
+    body: T,
+    http_range: Option<HttpRange>,
+) -> (
+    Result<BodyRange<T::Slice>, UnsatisfiableRange>,
+    RangeResponseKind,
+) {
+    let result = serve_file_with_http_range(body, http_range);
+    let kind = match &result {
+        Ok(body_range) if body_range.header.is_some() => RangeResponseKind::Partial,
+        Ok(_) => RangeResponseKind::Full,
+        Err(_) => RangeResponseKind::Unsatisfiable,
+    };
+
+    (result, kind)
+}
+
+/// Like [`serve_file_with_http_range`], but hands the whole `body` to `on_full`
+/// when no [`HttpRange`] is provided, instead of serving it.
+///
+/// This allows answering a request without a `Range` header differently, for
+/// instance with a cached representation or a redirect, while a range request
+/// is still sliced as usual. `on_full` is only called when `http_range` is `None`:
+/// a range that is ignored, such as a suffix-range on an empty representation,
+/// is still served as a [`ServedBody::Range`].
+///
+/// [`HttpRange`]: crate::headers::range::HttpRange
+pub fn serve_file_with_http_range_or_else<T: Sliceable, R>(
+    body: T,
+    http_range: Option<HttpRange>,
+    on_full: impl FnOnce(T) -> R,
+) -> Result<ServedBody<T::Slice, R>, UnsatisfiableRange> {
+    match http_range {
+        Some(http_range) => {
+            serve_file_with_http_range(body, Some(http_range)).map(ServedBody::Range)
+        }
+        None => Ok(ServedBody::Full(on_full(body))),
+    }
+}
+
+/// The body produced by [`serve_file_with_http_range_or_else`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServedBody<T, R> {
+    /// The requested range of the body.
+    Range(BodyRange<T>),
+    /// The value returned by `on_full`, as no range was requested.
+    Full(R),
+}
+
+/// The kind of response produced by [`serve_file_with_response_kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RangeResponseKind {
+    /// The full representation is served, with `200 OK`.
+    Full,
+    /// A range of the representation is served, with `206 Partial Content`.
+    Partial,
+    /// The requested range is not satisfiable, with `416 Range Not Satisfiable`.
+    Unsatisfiable,
+}
+
+impl RangeResponseKind {
+    /// Returns the status code of the response.
+    #[cfg(feature = "http")]
+    pub fn status_code(&self) -> http::StatusCode {
+        match self {
+            RangeResponseKind::Full => http::StatusCode::OK,
+            RangeResponseKind::Partial => http::StatusCode::PARTIAL_CONTENT,
+            RangeResponseKind::Unsatisfiable => http::StatusCode::RANGE_NOT_SATISFIABLE,
+        }
+    }
+}
+
+/// Like [`serve_file_with_http_range`], but lets the caller declare whether ranges
+/// are supported for `body`.
+///
+/// With [`AcceptRanges::None`] the provided [`HttpRange`] is ignored and the full body
+/// is served. The choice is available through [`BodyRange::accept_ranges`], so that the
+/// `Accept-Ranges` header can be set accordingly.
+///
+/// [`HttpRange`]: crate::headers::range::HttpRange
+/// [`AcceptRanges::None`]: crate::headers::accept_ranges::AcceptRanges::None
+pub fn serve_file_with_accept_ranges<T: Sliceable>(
+    body: T,
+    http_range: Option<HttpRange>,
+    accept_ranges: AcceptRanges,
+) -> Result<BodyRange<T::Slice>, UnsatisfiableRange> {
+    let http_range = match accept_ranges {
+        AcceptRanges::Bytes => http_range,
+        AcceptRanges::None => None,
+    };
+
+    let mut body_range = serve_file_with_http_range(body, http_range)?;
+    body_range.accept_ranges = accept_ranges;
+    Ok(body_range)
+}
+
+/// Like [`serve_file_with_http_range`], but for a `body` stored with the provided
+/// `content_encoding`, such as a precompressed `gzip` file.
+///
+/// Per [RFC 9110 Section 14.1.2], the ranges of a response with a `Content-Encoding`
+/// refer to the encoded bytes. So the [`HttpRange`] is resolved against the encoded
+/// `body` as it is, and the encoding is carried by the [`BodyRange`], see
+/// [`BodyRange::content_encoding`], so that the response cannot omit it.
+///
+/// Choosing the encoding according to the `Accept-Encoding` of the request, and
+/// setting `Vary: Accept-Encoding` accordingly, is up to the caller.
+///
+/// [`HttpRange`]: crate::headers::range::HttpRange
+/// [RFC 9110 Section 14.1.2]: https://www.rfc-editor.org/rfc/rfc9110#section-14.1.2
+#[cfg(feature = "http")]
+pub fn serve_precompressed<T: Sliceable>(
+    body: T,
+    content_encoding: HeaderValue,
+    http_range: Option<HttpRange>,
+) -> Result<BodyRange<T::Slice>, UnsatisfiableRange> {
+    let mut body_range = serve_file_with_http_range(body, http_range)?;
+    body_range.content_encoding = Some(content_encoding);
+    Ok(body_range)
+}
+
+/// Returns one [`BodyRange`] of [`Bytes`] per coalesced range of the provided [`HttpRanges`].
+///
+/// Overlapping and adjacent ranges are merged and unsatisfiable ones are dropped,
+/// see [`HttpRanges::coalesce`]. Only if no range remains is
+/// [`ServeRangesError::Unsatisfiable`] returned, as required by [RFC 9110 Section 15.5.17]. If the coalesced ranges add up to more than `max_total_bytes`,
+/// [`ServeRangesError::TooManyBytes`] is returned instead: passing the size of `body`
+/// ensures that no more than the whole body is ever served.
+///
+/// If the `tracing` feature is enabled, the requested ranges and the outcome are
+/// logged as `debug` events.
+///
+/// [`HttpRanges`]: crate::headers::ranges::HttpRanges
+/// [`HttpRanges::coalesce`]: crate::headers::ranges::HttpRanges::coalesce
+/// [RFC 9110 Section 15.5.17]: https://www.rfc-editor.org/rfc/rfc9110#section-15.5.17
+pub fn serve_file_with_http_ranges(
+    body: Bytes,
+    http_ranges: Option<HttpRanges>,
+    max_total_bytes: u64,
+) -> Result<Vec<BodyRange<Bytes>>, ServeRangesError> {
+    let size = u64::try_from(body.len()).expect("we do not support 128bit usize");
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(size, ranges = ?http_ranges, "serving multi-range request");
+
+    let Some(http_ranges) = http_ranges else {
+        #[cfg(feature = "tracing")]
+        tracing::debug!("serving full content");
+        return Ok(vec![BodyRange::new(body, None)]);
+    };
+
+    let ranges = http_ranges.coalesce(size);
+    if ranges.is_empty() {
+        // Same as `file_range`: a non-zero suffix-range on an empty
+        // representation is ignored and the full (empty) body is served.
+        if size == 0
+            && http_ranges
+                .iter()
+                .any(|range| matches!(range, HttpRange::Suffix(suffix) if *suffix > 0))
+        {
+            #[cfg(feature = "tracing")]
+            tracing::debug!("serving full content");
+            return Ok(vec![BodyRange::new(body, None)]);
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(size, "ranges not satisfiable");
+        let content_range = HttpContentRange::Unsatisfiable(Unsatisfiable::new(size));
+        return Err(ServeRangesError::Unsatisfiable(UnsatisfiableRange(
+            content_range,
+        )));
+    }
+
+    let total_bytes = ranges
+        .iter()
+        .map(|range| range.end() - range.start() + 1)
+        .fold(0u64, u64::saturating_add);
+    if total_bytes > max_total_bytes {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            total_bytes,
+            max_total_bytes,
+            "ranges exceed the allowed bytes"
+        );
+        return Err(ServeRangesError::TooManyBytes {
+            total_bytes,
+            max_total_bytes,
+        });
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        ranges = ?ranges,
+        total_bytes,
+        "serving partial content"
+    );
+
+    ranges
+        .into_iter()
+        .map(|range| {
+            let bound = resolved_bound(range, size).map_err(ServeRangesError::Unsatisfiable)?;
+            let start = usize::try_from(range.start()).expect("u64 doesn't fit usize");
+            let end = usize::try_from(range.end()).expect("u64 doesn't fit usize");
+
+            Ok(BodyRange::new(
+                Bytes::slice(&body, start..=end),
+                Some(HttpContentRange::Bound(bound)),
+            ))
+        })
+        .collect()
+}
+
+/// Like [`serve_file_with_http_ranges`], but applies the limits of the provided [`RangeConfig`].
+///
+/// The ranges are ignored, and the full content is served, if there are more than
+/// [`RangeConfig::max_ranges`] of them or if `body` is smaller than
+/// [`RangeConfig::min_range_size`], as RFC 9110 allows a server to ignore the
+/// `Range` header. Use [`HttpRanges::parse_with_config`] to also apply
+/// [`RangeConfig::max_header_len`] when parsing the header.
+///
+/// [`HttpRanges::parse_with_config`]: crate::headers::ranges::HttpRanges::parse_with_config
+pub fn serve_file_with_http_ranges_and_config(
+    body: Bytes,
+    http_ranges: Option<HttpRanges>,
+    max_total_bytes: u64,
+    config: &RangeConfig,
+) -> Result<Vec<BodyRange<Bytes>>, ServeRangesError> {
+    let size = u64::try_from(body.len()).expect("we do not support 128bit usize");
+    let http_ranges = http_ranges.filter(|http_ranges| {
+        let ignored = http_ranges.len() > config.max_ranges || size < config.min_range_size;
+
+        #[cfg(feature = "tracing")]
+        if ignored {
+            tracing::debug!(
+                ranges = http_ranges.len(),
+                max_ranges = config.max_ranges,
+                size,
+                min_range_size = config.min_range_size,
+                "ignoring ranges"
+            );
+        }
+
+        !ignored
+    });
+
+    serve_file_with_http_ranges(body, http_ranges, max_total_bytes)
 }
 
 /// Returns a [`ContentRange`] if the provided [`HttpRange`] is satisfiable, otherwise it returns [`UnsatisfiableRange`].
 ///
+/// The last valid byte index is `size - 1`. A range ending at or past `size`, such as
+/// `bytes=0-100` for a 100 bytes representation, is clamped to `size - 1` as required by
+/// [RFC 9110 Section 14.1.2]: only the start of a range decides whether it is satisfiable.
+///
 /// [`HttpRange`]: crate::headers::range::HttpRange
+/// [RFC 9110 Section 14.1.2]: https://www.rfc-editor.org/rfc/rfc9110#section-14.1.2
 pub fn file_range(
     size: u64,
     http_range: Option<HttpRange>,
@@ -45,59 +408,325 @@ pub fn file_range(
         });
     };
 
-    let range = match http_range {
-        HttpRange::StartingPoint(start) if start < size => start..size,
-        HttpRange::Range(range) if range.start() < size => {
-            range.start()..range.end().saturating_add(1).min(size)
-        }
-        HttpRange::Suffix(suffix) if suffix > 0 && size > 0 => size.saturating_sub(suffix)..size,
+    let range = match http_range.resolve(size) {
+        Some(range) => range,
         // A non-zero suffix-range is satisfiable even when the representation
         // is empty (RFC 9110 Section 14.1.2), but the `Content-Range` of a
         // 206 cannot be expressed for an empty body. Ignore the range and
         // serve the full (empty) representation instead, as permitted by
         // RFC 9110 Section 14.2.
-        HttpRange::Suffix(suffix) if suffix > 0 => {
+        None if matches!(http_range, HttpRange::Suffix(suffix) if suffix > 0) => {
             return Ok(ContentRange {
                 header: None,
                 range: 0..size,
             });
         }
-        _ => {
+        None => {
             let content_range = HttpContentRange::Unsatisfiable(Unsatisfiable::new(size));
             return Err(UnsatisfiableRange(content_range));
         }
     };
 
-    let content_range =
-        HttpContentRange::Bound(Bound::new(range.start..=range.end - 1, Some(size)).unwrap());
+    let content_range = HttpContentRange::Bound(resolved_bound(range, size)?);
 
     Ok(ContentRange {
         header: Some(content_range),
-        range,
+        range: range.start()..range.end() + 1,
     })
 }
 
+/// Like [`file_range`], but takes and returns sizes and offsets as `usize`, for
+/// representations whose size is tracked as a `usize`, such as in-memory bodies.
+///
+/// The returned half-open range can be used directly to slice the body. The
+/// `u64` based [`file_range`] remains the canonical API: the `Content-Range`
+/// header is still expressed in `u64`. [`serve_file_with_http_range`] does not
+/// need this, since it takes the size from the body itself.
+///
+/// [`HttpRange`]: crate::headers::range::HttpRange
+pub fn file_range_usize(
+    size: usize,
+    http_range: Option<HttpRange>,
+) -> Result<(Range<usize>, Option<HttpContentRange>), UnsatisfiableRange> {
+    let content_range = file_range(
+        u64::try_from(size).expect("we do not support 128bit usize"),
+        http_range,
+    )?;
+    // The range never extends past `size`, so it fits a `usize`.
+    let start = usize::try_from(content_range.range.start).expect("u64 doesn't fit usize");
+    let end = usize::try_from(content_range.range.end).expect("u64 doesn't fit usize");
+
+    Ok((start..end, content_range.header))
+}
+
+/// Builds the [`Bound`] of a `range` resolved against a representation of `size` bytes.
+///
+/// A resolved range always ends before `size`, so this cannot fail unless the
+/// resolution is broken: debug builds panic, while release builds answer with
+/// a `416` rather than panicking while serving a request.
+fn resolved_bound(range: OrderedRange, size: u64) -> Result<Bound, UnsatisfiableRange> {
+    let bound = Bound::new(range.start()..=range.end(), Some(size));
+    debug_assert!(
+        bound.is_ok(),
+        "the resolved range {range} does not fit a representation of {size} bytes"
+    );
+
+    bound.map_err(|_| UnsatisfiableRange(HttpContentRange::unsatisfiable(size)))
+}
+
+/// Like [`file_range`], but ignores the provided [`HttpRange`] if `size` is below `min_range_size`.
+///
+/// Serving a partial response for small files adds overhead with little benefit, and
+/// per [RFC 9110 Section 14.2] a server may always ignore the `Range` header and serve
+/// the full representation. Below the threshold the returned [`ContentRange`] has no
+/// header, even for ranges that would otherwise be unsatisfiable.
+///
+/// [`HttpRange`]: crate::headers::range::HttpRange
+/// [RFC 9110 Section 14.2]: https://www.rfc-editor.org/rfc/rfc9110#section-14.2
+pub fn file_range_with_policy(
+    size: u64,
+    http_range: Option<HttpRange>,
+    min_range_size: u64,
+) -> Result<ContentRange, UnsatisfiableRange> {
+    if size < min_range_size {
+        return file_range(size, None);
+    }
+
+    file_range(size, http_range)
+}
+
+/// The default value of [`RangeConfig::max_ranges`].
+pub const DEFAULT_MAX_RANGES: usize = 16;
+
+/// The default value of [`RangeConfig::min_range_size`].
+pub const DEFAULT_MIN_RANGE_SIZE: u64 = 0;
+
+/// The default value of [`RangeConfig::tiny_range_ratio`].
+pub const DEFAULT_TINY_RANGE_RATIO: u64 = 1024 * 1024;
+
+/// The limits applied when serving range requests.
+///
+/// This struct is `#[non_exhaustive]`, since new limits may be added: start from
+/// [`RangeConfig::default`] and set the fields to change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RangeConfig {
+    /// The maximum number of ranges honored in a single request, see
+    /// [`serve_file_with_http_ranges_and_config`] and [`HttpRanges::parse_with_config`].
+    ///
+    /// [`HttpRanges::parse_with_config`]: crate::headers::ranges::HttpRanges::parse_with_config
+    pub max_ranges: usize,
+    /// The maximum length of a `Range` header value, see [`HttpRanges::parse_with_config`].
+    ///
+    /// [`HttpRanges::parse_with_config`]: crate::headers::ranges::HttpRanges::parse_with_config
+    pub max_header_len: usize,
+    /// The size below which ranges are ignored, see [`file_range_with_policy`].
+    pub min_range_size: u64,
+    /// How many times larger than a range the representation must be for the
+    /// range to be considered tiny, see [`RangeConfig::is_tiny_range`].
+    /// `0` disables the check.
+    pub tiny_range_ratio: u64,
+}
+
+impl RangeConfig {
+    /// Checks whether a range of `length` bytes is tiny relative to a representation
+    /// of `size` bytes, i.e. whether the representation is more than
+    /// [`RangeConfig::tiny_range_ratio`] times larger.
+    ///
+    /// With the default ratio, a 1 byte range of a file larger than 1 MiB is tiny.
+    pub fn is_tiny_range(&self, length: u64, size: u64) -> bool {
+        self.tiny_range_ratio != 0 && length.saturating_mul(self.tiny_range_ratio) < size
+    }
+}
+
+impl Default for RangeConfig {
+    fn default() -> Self {
+        Self {
+            max_ranges: DEFAULT_MAX_RANGES,
+            max_header_len: DEFAULT_MAX_HEADER_LEN,
+            min_range_size: DEFAULT_MIN_RANGE_SIZE,
+            tiny_range_ratio: DEFAULT_TINY_RANGE_RATIO,
+        }
+    }
+}
+
+/// Checks whether `new_range` continues a download whose last response carried `last_content_range`.
+///
+/// The previously delivered bytes are described by `last_content_range`, the next
+/// request by `new_range`. A [`HttpRange::Suffix`] can only be checked if the size
+/// of the representation is known from `last_content_range`.
+///
+/// [`HttpRange::Suffix`]: crate::headers::range::HttpRange::Suffix
+pub fn resume_check(last_content_range: HttpContentRange, new_range: HttpRange) -> ResumeDecision {
+    let HttpContentRange::Bound(bound) = last_content_range else {
+        return ResumeDecision::Restart;
+    };
+    let last_range = bound.range();
+
+    let new_start = match new_range {
+        HttpRange::StartingPoint(start) => start,
+        HttpRange::Range(range) => range.start(),
+        HttpRange::Suffix(suffix) => match bound.size() {
+            Some(size) => size.saturating_sub(suffix),
+            None => return ResumeDecision::Restart,
+        },
+    };
+
+    if last_range.end().checked_add(1) == Some(new_start) {
+        ResumeDecision::Continue
+    } else if new_start != 0 && (last_range.start()..=last_range.end()).contains(&new_start) {
+        ResumeDecision::Overlap
+    } else {
+        ResumeDecision::Restart
+    }
+}
+
+/// The outcome of [`resume_check`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeDecision {
+    /// The new range starts right after the previously delivered bytes.
+    Continue,
+    /// The new range starts from the beginning, leaves a gap, or cannot be related
+    /// to the previously delivered bytes.
+    Restart,
+    /// The new range starts within the previously delivered bytes.
+    Overlap,
+}
+
+/// Checks whether an uploaded chunk continues the `stored_len` bytes already stored.
+///
+/// Resumable uploads send each chunk of a `PUT` or `PATCH` request with a
+/// `Content-Range` described by `content_range`. `expected_size` is the size
+/// of the complete representation, if it was declared by a previous chunk.
+pub fn upload_check(
+    content_range: Bound,
+    stored_len: u64,
+    expected_size: Option<u64>,
+) -> UploadDecision {
+    if let (Some(expected_size), Some(size)) = (expected_size, content_range.size())
+        && expected_size != size
+    {
+        return UploadDecision::SizeMismatch;
+    }
+
+    let range = content_range.range();
+    // A chunk with a known size always ends before it, see `Bound::new`.
+    if expected_size.is_some_and(|expected_size| range.end() >= expected_size) {
+        return UploadDecision::ExceedsSize;
+    }
+
+    match range.start().cmp(&stored_len) {
+        Ordering::Equal => match range.end().checked_add(1) {
+            Some(new_len) => UploadDecision::Accept { new_len },
+            None => UploadDecision::ExceedsSize,
+        },
+        Ordering::Greater => UploadDecision::Gap,
+        Ordering::Less => UploadDecision::Overlap,
+    }
+}
+
+/// The outcome of [`upload_check`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadDecision {
+    /// The chunk starts right after the stored bytes, which will be `new_len` bytes
+    /// long once it is appended.
+    Accept { new_len: u64 },
+    /// The chunk starts after the end of the stored bytes, leaving a gap.
+    Gap,
+    /// The chunk starts within the stored bytes.
+    Overlap,
+    /// The chunk declares a different size for the complete representation.
+    SizeMismatch,
+    /// The chunk ends at or past the expected size of the complete representation,
+    /// or the stored bytes would be longer than `u64::MAX`.
+    ExceedsSize,
+}
+
 /// A container for the payload slice and the optional `Content-Range` header.
 ///
 /// The header is `None` only if the body was not sliced.
 ///
-/// If the `axum` feature is enabled this struct also implements `IntoResponse`.
+/// If the `axum` feature is enabled this struct also implements `IntoResponse`,
+/// which sets the `Content-Length` header to the length of the sliced body, the
+/// `Accept-Ranges` header according to [`BodyRange::accept_ranges`], the
+/// `Content-Encoding` header if any and, unless disabled through
+/// [`BodyRange::with_vary_range`], `Vary: Range`.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BodyRange<T> {
     body: T,
     header: Option<HttpContentRange>,
+    accept_ranges: AcceptRanges,
+    vary_range: bool,
+    #[cfg(feature = "http")]
+    content_encoding: Option<HeaderValue>,
 }
 
 impl<T> BodyRange<T> {
+    /// Creates a new [`BodyRange`] from an already sliced body.
+    ///
+    /// The `header` should be `None` if the body was not sliced.
+    pub fn new(body: T, header: Option<HttpContentRange>) -> Self {
+        Self {
+            body,
+            header,
+            accept_ranges: AcceptRanges::Bytes,
+            vary_range: true,
+            #[cfg(feature = "http")]
+            content_encoding: None,
+        }
+    }
+
+    /// Sets whether the response should carry `Vary: Range`, which is the default.
+    ///
+    /// When ranges are supported, the response depends on the `Range` header of the
+    /// request, so `Vary: Range` prevents shared caches from serving a
+    /// `206 Partial Content` to a client that asked for the full representation.
+    /// It is never sent with [`AcceptRanges::None`].
+    ///
+    /// [`AcceptRanges::None`]: crate::headers::accept_ranges::AcceptRanges::None
+    pub fn with_vary_range(mut self, vary_range: bool) -> Self {
+        self.vary_range = vary_range;
+        self
+    }
+
     /// Returns the sliced body.
     pub fn body(&self) -> &T {
         &self.body
     }
 
+    /// Compares the sliced body with `other`, regardless of the `Content-Range` header.
+    ///
+    /// ```
+    /// use bytes::Bytes;
+    /// use range_requests::{headers::range::HttpRange, serve_file_with_http_range};
+    ///
+    /// let body = Bytes::from_static(b"hello world");
+    /// let full = serve_file_with_http_range(body.clone(), None).unwrap();
+    /// let suffix = serve_file_with_http_range(body.clone(), Some(HttpRange::suffix(11))).unwrap();
+    ///
+    /// assert_ne!(full, suffix);
+    /// assert!(full.body_eq(&body));
+    /// assert!(suffix.body_eq(&body));
+    /// assert!(suffix.body_eq(b"hello world".as_slice()));
+    /// ```
+    pub fn body_eq<U>(&self, other: &U) -> bool
+    where
+        T: PartialEq<U>,
+        U: ?Sized,
+    {
+        self.body == *other
+    }
+
     pub fn into_body(self) -> T {
         self.body
     }
 
+    /// Consumes this [`BodyRange`], returning the sliced body and the optional
+    /// [`HttpContentRange`].
+    pub fn into_parts(self) -> (T, Option<HttpContentRange>) {
+        (self.body, self.header)
+    }
+
     /// Returns an option of [`HttpContentRange`].
     /// It is `None` if no range was applied to the body: either no
     /// [`HttpRange`] was provided, or the range was ignored because the
@@ -105,6 +734,24 @@ impl<T> BodyRange<T> {
     pub fn header(&self) -> Option<HttpContentRange> {
         self.header
     }
+
+    /// Returns whether ranges are supported for this body.
+    ///
+    /// It is [`AcceptRanges::Bytes`] unless the body was served through
+    /// [`serve_file_with_accept_ranges`] with [`AcceptRanges::None`].
+    ///
+    /// [`AcceptRanges::Bytes`]: crate::headers::accept_ranges::AcceptRanges::Bytes
+    /// [`AcceptRanges::None`]: crate::headers::accept_ranges::AcceptRanges::None
+    pub fn accept_ranges(&self) -> AcceptRanges {
+        self.accept_ranges
+    }
+
+    /// Returns the `Content-Encoding` of the body, if it was served through
+    /// [`serve_precompressed`].
+    #[cfg(feature = "http")]
+    pub fn content_encoding(&self) -> Option<&HeaderValue> {
+        self.content_encoding.as_ref()
+    }
 }
 
 /// A container for the payload range and the optional `Content-Range` header.
@@ -117,6 +764,34 @@ pub struct ContentRange {
 }
 
 impl ContentRange {
+    /// Creates a new [`ContentRange`], returning [`InvalidOrderedRange`] if
+    /// `range.start` is greater than `range.end`.
+    ///
+    /// The `header` should be `None` if the body is not going to be sliced.
+    pub fn new(
+        header: Option<HttpContentRange>,
+        range: Range<u64>,
+    ) -> Result<Self, InvalidOrderedRange> {
+        OrderedRange::new(range.start..=range.end)?;
+
+        Ok(Self { header, range })
+    }
+
+    /// Creates a [`ContentRange`] that slices the body as described by the [`Bound`].
+    ///
+    /// Returns `None` if the [`Bound`] ends at `u64::MAX`, since the exclusive end
+    /// of the range would not fit a `u64`.
+    ///
+    /// [`Bound`]: crate::headers::content_range::Bound
+    pub fn from_bound(bound: Bound) -> Option<Self> {
+        let range = bound.range();
+
+        Some(Self {
+            header: Some(HttpContentRange::Bound(bound)),
+            range: range.start()..range.end().checked_add(1)?,
+        })
+    }
+
     /// Returns an option of [`HttpContentRange`].
     /// It is `None` if no range was applied to the body: either no
     /// [`HttpRange`] was provided, or the range was ignored because the
@@ -126,16 +801,56 @@ impl ContentRange {
     }
 
     /// Returns a [`Range`] of `u64` useful to manually slice the response body.
+    ///
+    /// Unlike the inclusive ranges used by the headers, this range is half-open,
+    /// so it can be used directly for slice indexing.
     pub fn range(&self) -> &Range<u64> {
         &self.range
     }
+
+    /// Returns the `(start, end)` points of [`ContentRange::range`] as plain integers.
+    ///
+    /// Like [`ContentRange::range`], `end` is exclusive, so an empty body is `(0, 0)`.
+    pub fn bounds(&self) -> (u64, u64) {
+        (self.range.start, self.range.end)
+    }
+
+    /// Merges two [`ContentRange`]s of the same representation, such as the ones of
+    /// two sequential downloads, into one covering both.
+    ///
+    /// Returns `None` unless both carry a [`Bound`] with the same size and the
+    /// ranges are contiguous, in either order.
+    ///
+    /// [`Bound`]: crate::headers::content_range::Bound
+    pub fn try_merge(&self, other: &ContentRange) -> Option<ContentRange> {
+        let (Some(HttpContentRange::Bound(bound)), Some(HttpContentRange::Bound(other_bound))) =
+            (self.header, other.header)
+        else {
+            return None;
+        };
+        if bound.size() != other_bound.size() {
+            return None;
+        }
+
+        let range = if self.range.end == other.range.start {
+            self.range.start..other.range.end
+        } else if other.range.end == self.range.start {
+            other.range.start..self.range.end
+        } else {
+            return None;
+        };
+
+        Bound::from_exclusive(range, bound.size())
+            .ok()
+            .and_then(ContentRange::from_bound)
+    }
 }
 
 /// An unsatisfiable range request.
 ///
 /// If the `axum` feature is enabled this struct also implements `IntoResponse`.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct UnsatisfiableRange(HttpContentRange);
+pub struct UnsatisfiableRange(pub(crate) HttpContentRange);
 
 impl UnsatisfiableRange {
     /// Returns the [`HttpContentRange`] header.
@@ -144,25 +859,236 @@ impl UnsatisfiableRange {
     }
 }
 
+/// A response to a range request, carrying its status and all the range related
+/// headers.
+///
+/// It unifies the two outcomes of [`serve_file_with_http_range`]: a [`BodyRange`] and
+/// an [`UnsatisfiableRange`] both convert into it, and so does the [`Result`] holding
+/// either of them.
+///
+/// If the `axum` feature is enabled this struct also implements `IntoResponse`, which
+/// sets the `Content-Range` and `Content-Encoding` headers if any, `Accept-Ranges`
+/// and, unless disabled through
+/// [`RangeResponse::with_vary_range`], `Vary: Range`. Unlike [`UnsatisfiableRange`],
+/// a `416 Range Not Satisfiable` response also advertises `Accept-Ranges`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeResponse<T> {
+    body: Option<T>,
+    header: Option<HttpContentRange>,
+    accept_ranges: AcceptRanges,
+    vary_range: bool,
+    #[cfg(feature = "http")]
+    content_encoding: Option<HeaderValue>,
+}
+
+impl<T> RangeResponse<T> {
+    /// Sets the `Accept-Ranges` header of the response.
+    ///
+    /// This only changes what is advertised: the body is served as it is.
+    pub fn with_accept_ranges(mut self, accept_ranges: AcceptRanges) -> Self {
+        self.accept_ranges = accept_ranges;
+        self
+    }
+
+    /// Sets whether the response should carry `Vary: Range`, see
+    /// [`BodyRange::with_vary_range`].
+    pub fn with_vary_range(mut self, vary_range: bool) -> Self {
+        self.vary_range = vary_range;
+        self
+    }
+
+    /// Returns the [`RangeResponseKind`] of the response.
+    pub fn kind(&self) -> RangeResponseKind {
+        match self.header {
+            None => RangeResponseKind::Full,
+            Some(HttpContentRange::Bound(_)) => RangeResponseKind::Partial,
+            Some(HttpContentRange::Unsatisfiable(_)) => RangeResponseKind::Unsatisfiable,
+        }
+    }
+
+    /// Returns the status code of the response.
+    #[cfg(feature = "http")]
+    pub fn status_code(&self) -> http::StatusCode {
+        self.kind().status_code()
+    }
+
+    /// Returns the `Content-Range` header, which is `None` if the full
+    /// representation is served.
+    pub fn content_range(&self) -> Option<HttpContentRange> {
+        self.header
+    }
+
+    /// Returns the `Accept-Ranges` header.
+    pub fn accept_ranges(&self) -> AcceptRanges {
+        self.accept_ranges
+    }
+
+    /// Returns the body, which is `None` for a `416 Range Not Satisfiable` response.
+    pub fn body(&self) -> Option<&T> {
+        self.body.as_ref()
+    }
+
+    /// Consumes this [`RangeResponse`], returning the body.
+    pub fn into_body(self) -> Option<T> {
+        self.body
+    }
+}
+
+impl<T> From<BodyRange<T>> for RangeResponse<T> {
+    fn from(body_range: BodyRange<T>) -> Self {
+        Self {
+            body: Some(body_range.body),
+            header: body_range.header,
+            accept_ranges: body_range.accept_ranges,
+            vary_range: body_range.vary_range,
+            #[cfg(feature = "http")]
+            content_encoding: body_range.content_encoding,
+        }
+    }
+}
+
+impl<T> From<UnsatisfiableRange> for RangeResponse<T> {
+    fn from(unsatisfiable_range: UnsatisfiableRange) -> Self {
+        Self {
+            body: None,
+            header: Some(unsatisfiable_range.0),
+            accept_ranges: AcceptRanges::Bytes,
+            vary_range: true,
+            #[cfg(feature = "http")]
+            content_encoding: None,
+        }
+    }
+}
+
+impl<T> From<Result<BodyRange<T>, UnsatisfiableRange>> for RangeResponse<T> {
+    fn from(result: Result<BodyRange<T>, UnsatisfiableRange>) -> Self {
+        match result {
+            Ok(body_range) => body_range.into(),
+            Err(unsatisfiable_range) => unsatisfiable_range.into(),
+        }
+    }
+}
+
+/// The Errors that may occur in [`serve_file_with_http_ranges`] and
+/// [`serve_file_with_http_ranges_and_config`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ServeRangesError {
+    /// None of the requested ranges is satisfiable.
+    #[error("none of the requested ranges is satisfiable")]
+    Unsatisfiable(UnsatisfiableRange),
+    /// The coalesced ranges add up to more than the allowed number of bytes.
+    #[error(
+        "requested ranges add up to {total_bytes} bytes, more than the allowed {max_total_bytes}"
+    )]
+    TooManyBytes {
+        total_bytes: u64,
+        max_total_bytes: u64,
+    },
+}
+
 #[cfg(feature = "axum")]
 mod axum {
-    use crate::{BodyRange, UnsatisfiableRange};
+    use crate::{
+        BodyRange, RangeResponse, UnsatisfiableRange, headers::accept_ranges::AcceptRanges,
+    };
 
-    use axum_core::response::{IntoResponse, Response};
+    use axum_core::{
+        body::Body,
+        response::{IntoResponse, Response},
+    };
     use bytes::Bytes;
-    use http::{HeaderValue, StatusCode, header::CONTENT_RANGE};
+    use http::{
+        HeaderValue, StatusCode,
+        header::{ACCEPT_RANGES, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_RANGE, VARY},
+    };
 
     impl IntoResponse for BodyRange<Bytes> {
         fn into_response(self) -> Response {
-            match self.header {
+            let accept_ranges = (ACCEPT_RANGES, HeaderValue::from(&self.accept_ranges));
+            let content_length = (CONTENT_LENGTH, HeaderValue::from(self.body.len()));
+            let vary_range = self.vary_range && self.accept_ranges == AcceptRanges::Bytes;
+            let mut response = match self.header {
                 Some(range) => (
                     StatusCode::PARTIAL_CONTENT,
-                    [(CONTENT_RANGE, HeaderValue::from(&range))],
+                    [
+                        accept_ranges,
+                        content_length,
+                        (CONTENT_RANGE, HeaderValue::from(&range)),
+                    ],
                     self.body,
                 )
                     .into_response(),
-                None => (StatusCode::OK, self.body).into_response(),
+                None => {
+                    (StatusCode::OK, [accept_ranges, content_length], self.body).into_response()
+                }
+            };
+            if let Some(content_encoding) = self.content_encoding {
+                response
+                    .headers_mut()
+                    .insert(CONTENT_ENCODING, content_encoding);
+            }
+            if vary_range {
+                response
+                    .headers_mut()
+                    .append(VARY, HeaderValue::from_static("Range"));
+            }
+            response
+        }
+    }
+
+    impl BodyRange<Bytes> {
+        /// Converts this [`BodyRange`] into the response to a `HEAD` request.
+        ///
+        /// The status and headers are the same as the `GET` response produced by
+        /// `IntoResponse`, including the `Content-Length` equal to the length of the
+        /// sliced body, but the body itself is omitted.
+        pub fn into_head_response(self) -> Response {
+            let mut response = self.into_response();
+            *response.body_mut() = Body::empty();
+            response
+        }
+    }
+
+    impl UnsatisfiableRange {
+        /// Converts this [`UnsatisfiableRange`] into the response to a `HEAD` request.
+        ///
+        /// This is the same as the `GET` response, since a `416 Range Not Satisfiable`
+        /// never carries a body.
+        pub fn into_head_response(self) -> Response {
+            self.into_response()
+        }
+    }
+
+    impl IntoResponse for RangeResponse<Bytes> {
+        fn into_response(self) -> Response {
+            let status = self.status_code();
+            let vary_range = self.vary_range && self.accept_ranges == AcceptRanges::Bytes;
+            let body = self.body.unwrap_or_default();
+            let mut response = (
+                status,
+                [
+                    (ACCEPT_RANGES, HeaderValue::from(&self.accept_ranges)),
+                    (CONTENT_LENGTH, HeaderValue::from(body.len())),
+                ],
+                body,
+            )
+                .into_response();
+            if let Some(range) = self.header {
+                response
+                    .headers_mut()
+                    .insert(CONTENT_RANGE, HeaderValue::from(&range));
+            }
+            if let Some(content_encoding) = self.content_encoding {
+                response
+                    .headers_mut()
+                    .insert(CONTENT_ENCODING, content_encoding);
+            }
+            if vary_range {
+                response
+                    .headers_mut()
+                    .append(VARY, HeaderValue::from_static("Range"));
             }
+            response
         }
     }
 
@@ -0,0 +1,350 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use http::HeaderValue;
+
+use crate::headers::{
+    ParseHttpRangeOrContentRangeError,
+    content_range::{Bound, HttpContentRange},
+    is_tchar,
+    ranges::{HttpRanges, coalesce},
+};
+
+const MAX_BOUNDARY_LEN: usize = 70;
+
+/// A builder for a `multipart/byteranges` response body.
+///
+/// Per [RFC 9110 Section 14.6], a response to a request with multiple ranges
+/// carries each range in a separate part, with its own `Content-Range` header,
+/// separated by a boundary that is announced in the `Content-Type` of the response.
+///
+/// [RFC 9110 Section 14.6]: https://www.rfc-editor.org/rfc/rfc9110#section-14.6
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartByteranges {
+    boundary: String,
+    content_type: Option<HeaderValue>,
+    parts: Vec<(Bound, Bytes)>,
+}
+
+impl MultipartByteranges {
+    /// Creates an empty [`MultipartByteranges`] with a random boundary.
+    pub fn new() -> Self {
+        Self {
+            boundary: random_boundary(),
+            content_type: None,
+            parts: Vec::new(),
+        }
+    }
+
+    /// Replaces the random boundary with the provided one, for instance to get a
+    /// deterministic body in tests.
+    ///
+    /// The boundary must be 1 to 70 characters long, only contain the characters
+    /// allowed by [RFC 2046 Section 5.1.1] and not end with a space.
+    ///
+    /// [RFC 2046 Section 5.1.1]: https://www.rfc-editor.org/rfc/rfc2046#section-5.1.1
+    pub fn with_boundary(mut self, boundary: impl Into<String>) -> Result<Self, InvalidBoundary> {
+        let boundary = boundary.into();
+        if !is_valid_boundary(&boundary) {
+            return Err(InvalidBoundary(boundary));
+        }
+
+        self.boundary = boundary;
+        Ok(self)
+    }
+
+    /// Sets the `Content-Type` of the representation, which is sent in every part.
+    pub fn with_content_type(mut self, content_type: HeaderValue) -> Self {
+        self.content_type = Some(content_type);
+        self
+    }
+
+    /// Appends a part containing the `body` slice described by `content_range`.
+    pub fn push(&mut self, content_range: Bound, body: Bytes) {
+        self.parts.push((content_range, body));
+    }
+
+    /// Returns the boundary separating the parts.
+    pub fn boundary(&self) -> &str {
+        &self.boundary
+    }
+
+    /// Returns the `Content-Type` header value of the response,
+    /// i.e. `multipart/byteranges; boundary=...`.
+    ///
+    /// The boundary is quoted when it is not a `token`, for instance when it
+    /// contains a `:` or a space, as required by [RFC 9110 Section 5.6.6].
+    ///
+    /// [RFC 9110 Section 5.6.6]: https://www.rfc-editor.org/rfc/rfc9110#section-5.6.6
+    pub fn multipart_content_type(&self) -> HeaderValue {
+        // A valid boundary contains neither `"` nor `\`, so it never needs escaping.
+        let content_type = if self.boundary.bytes().all(is_tchar) {
+            format!("multipart/byteranges; boundary={}", self.boundary)
+        } else {
+            format!("multipart/byteranges; boundary=\"{}\"", self.boundary)
+        };
+
+        HeaderValue::from_maybe_shared(content_type)
+            .expect("the boundary only contains visible ASCII characters and spaces")
+    }
+
+    /// Returns the number of parts.
+    pub fn part_count(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Returns the length of the assembled body, including the delimiters and the
+    /// headers of every part, to be sent as `Content-Length`.
+    pub fn content_length(&self) -> u64 {
+        let boundary_len = self.boundary.len();
+        let content_type_len = self.content_type.as_ref().map_or(0, |content_type| {
+            "Content-Type: \r\n".len() + content_type.len()
+        });
+
+        let parts_len = self
+            .parts
+            .iter()
+            .map(|(content_range, part)| {
+                let content_range_len = HttpContentRange::Bound(*content_range).to_string().len();
+                "--\r\n".len()
+                    + boundary_len
+                    + content_type_len
+                    + "Content-Range: \r\n\r\n".len()
+                    + content_range_len
+                    + part.len()
+                    + "\r\n".len()
+            })
+            .sum::<usize>();
+        let close_delimiter_len = "----\r\n".len() + boundary_len;
+
+        u64::try_from(parts_len + close_delimiter_len).expect("we do not support 128bit usize")
+    }
+
+    /// Assembles the response body.
+    pub fn into_bytes(self) -> Bytes {
+        let capacity = usize::try_from(self.content_length()).expect("u64 doesn't fit usize");
+        let mut body = BytesMut::with_capacity(capacity);
+        for (content_range, part) in &self.parts {
+            body.put_slice(b"--");
+            body.put_slice(self.boundary.as_bytes());
+            body.put_slice(b"\r\n");
+            if let Some(content_type) = &self.content_type {
+                body.put_slice(b"Content-Type: ");
+                body.put_slice(content_type.as_bytes());
+                body.put_slice(b"\r\n");
+            }
+            body.put_slice(b"Content-Range: ");
+            body.put_slice(
+                HttpContentRange::Bound(*content_range)
+                    .to_string()
+                    .as_bytes(),
+            );
+            body.put_slice(b"\r\n\r\n");
+            body.put_slice(part);
+            body.put_slice(b"\r\n");
+        }
+        body.put_slice(b"--");
+        body.put_slice(self.boundary.as_bytes());
+        body.put_slice(b"--\r\n");
+
+        body.freeze()
+    }
+}
+
+impl Default for MultipartByteranges {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The `Content-Range`s of the parts of a `multipart/byteranges` response,
+/// as received by a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartRanges(Vec<HttpContentRange>);
+
+impl MultipartRanges {
+    /// Parses the `Content-Range` header of every part of a `multipart/byteranges`
+    /// `body` whose parts are separated by `boundary`.
+    pub fn parse(body: &[u8], boundary: &str) -> Result<Self, MultipartError> {
+        parse_parts(body, boundary)
+            .map(|parts| Self(parts.into_iter().map(|(header, _)| header).collect()))
+    }
+
+    /// Returns the `Content-Range` of every part, in the order they were received.
+    pub fn content_ranges(&self) -> &[HttpContentRange] {
+        &self.0
+    }
+
+    /// Checks whether the parts cover exactly the `requested` ranges.
+    ///
+    /// Every part must be a [`Bound`] and all of them must carry the same known
+    /// size. Since a server may reorder the ranges and merge the ones that
+    /// overlap or are adjacent, the coalesced parts are compared against
+    /// [`HttpRanges::coalesce`].
+    pub fn verify(&self, requested: &HttpRanges) -> bool {
+        let mut size = None;
+        let mut ranges = Vec::with_capacity(self.0.len());
+        for content_range in &self.0 {
+            let HttpContentRange::Bound(bound) = content_range else {
+                return false;
+            };
+            let Some(part_size) = bound.size() else {
+                return false;
+            };
+            if *size.get_or_insert(part_size) != part_size {
+                return false;
+            }
+
+            ranges.push(bound.range());
+        }
+
+        size.is_some_and(|size| coalesce(ranges) == requested.coalesce(size))
+    }
+}
+
+/// Splits a `multipart/byteranges` `body` whose parts are separated by `boundary`
+/// into the `Content-Range` and the payload of each part, in the order they were
+/// received.
+///
+/// This is the inverse of [`MultipartByteranges::into_bytes`]. The CRLF preceding
+/// each delimiter belongs to the delimiter, so it is not part of the payload,
+/// while the transport padding after a delimiter, the preamble and the epilogue
+/// are ignored. The close delimiter is required, with or without a trailing CRLF.
+pub fn parse_multipart_byteranges(
+    body: &[u8],
+    boundary: &str,
+) -> Result<Vec<(HttpContentRange, Bytes)>, MultipartError> {
+    parse_parts(body, boundary).map(|parts| {
+        parts
+            .into_iter()
+            .map(|(header, payload)| (header, Bytes::copy_from_slice(payload)))
+            .collect()
+    })
+}
+
+/// The Errors that may occur when parsing a `multipart/byteranges` body.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum MultipartError {
+    #[error("missing multipart delimiter")]
+    MissingDelimiter,
+    #[error("missing multipart close delimiter")]
+    MissingCloseDelimiter,
+    #[error("malformed multipart part")]
+    MalformedPart,
+    #[error("multipart part without a Content-Range header")]
+    MissingContentRange,
+    #[error(transparent)]
+    InvalidContentRange(#[from] ParseHttpRangeOrContentRangeError),
+}
+
+/// An error returned when a multipart boundary is not valid.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid multipart boundary: {0:?}")]
+pub struct InvalidBoundary(String);
+
+#[cfg(feature = "axum")]
+impl axum_core::response::IntoResponse for MultipartByteranges {
+    fn into_response(self) -> axum_core::response::Response {
+        let content_type = (http::header::CONTENT_TYPE, self.multipart_content_type());
+        let content_length = (
+            http::header::CONTENT_LENGTH,
+            HeaderValue::from(self.content_length()),
+        );
+        (
+            http::StatusCode::PARTIAL_CONTENT,
+            [content_type, content_length],
+            self.into_bytes(),
+        )
+            .into_response()
+    }
+}
+
+fn is_valid_boundary(boundary: &str) -> bool {
+    (1..=MAX_BOUNDARY_LEN).contains(&boundary.len())
+        && !boundary.ends_with(' ')
+        && boundary
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"'()+_,-./:=? ".contains(&b))
+}
+
+fn random_boundary() -> String {
+    // `RandomState` is randomly seeded, which is good enough to make the
+    // boundary unlikely to appear in the body.
+    let random = || RandomState::new().build_hasher().finish();
+    format!("{:016x}{:016x}", random(), random())
+}
+
+/// Splits a `multipart/byteranges` body into the `Content-Range` and the payload of each part.
+///
+/// The preamble before the first delimiter and the epilogue after the close
+/// delimiter are ignored, as required by [RFC 2046 Section 5.1.1].
+///
+/// [RFC 2046 Section 5.1.1]: https://www.rfc-editor.org/rfc/rfc2046#section-5.1.1
+fn parse_parts<'a>(
+    body: &'a [u8],
+    boundary: &str,
+) -> Result<Vec<(HttpContentRange, &'a [u8])>, MultipartError> {
+    let dash_boundary = [b"--", boundary.as_bytes()].concat();
+    // Every delimiter but the first one is preceded by a CRLF, which belongs
+    // to the delimiter rather than to the payload of the previous part.
+    let delimiter = [b"\r\n", dash_boundary.as_slice()].concat();
+
+    let mut rest = match body.strip_prefix(dash_boundary.as_slice()) {
+        Some(rest) => rest,
+        None => {
+            let position = find(body, &delimiter).ok_or(MultipartError::MissingDelimiter)?;
+            &body[position + delimiter.len()..]
+        }
+    };
+
+    let mut parts = Vec::new();
+    loop {
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+
+        // Transport padding may follow a delimiter.
+        let padding = rest
+            .iter()
+            .take_while(|&&b| b == b' ' || b == b'\t')
+            .count();
+        rest = rest[padding..]
+            .strip_prefix(b"\r\n")
+            .ok_or(MultipartError::MalformedPart)?;
+
+        let end = find(rest, &delimiter).ok_or(MultipartError::MissingCloseDelimiter)?;
+        parts.push(parse_part(&rest[..end])?);
+        rest = &rest[end + delimiter.len()..];
+    }
+}
+
+fn parse_part(part: &[u8]) -> Result<(HttpContentRange, &[u8]), MultipartError> {
+    let (headers, payload) = match part.strip_prefix(b"\r\n") {
+        Some(payload) => (&[][..], payload),
+        None => {
+            let position = find(part, b"\r\n\r\n").ok_or(MultipartError::MalformedPart)?;
+            (&part[..position], &part[position + 4..])
+        }
+    };
+    let headers = std::str::from_utf8(headers).map_err(|_| MultipartError::MalformedPart)?;
+
+    let mut content_range = None;
+    for line in headers.split("\r\n").filter(|line| !line.is_empty()) {
+        let (name, value) = line.split_once(':').ok_or(MultipartError::MalformedPart)?;
+        if name.eq_ignore_ascii_case(http::header::CONTENT_RANGE.as_str()) {
+            content_range = Some(value.parse::<HttpContentRange>()?);
+        }
+    }
+
+    let content_range = content_range.ok_or(MultipartError::MissingContentRange)?;
+    Ok((content_range, payload))
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
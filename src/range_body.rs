@@ -0,0 +1,96 @@
+use std::{
+    io::{self, SeekFrom},
+    ops::Range,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use bytes::Bytes;
+use futures_io::{AsyncRead, AsyncSeek};
+use http_body::{Body, Frame, SizeHint};
+
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// A streaming [`Body`] serving a range of a seekable [`AsyncRead`] source.
+///
+/// On the first poll the source is seeked to the start of the range, then it is
+/// read in chunks of at most 8 KiB until the end of the range is reached. The
+/// range is usually obtained from [`ContentRange::range`].
+///
+/// [`ContentRange::range`]: crate::ContentRange::range
+#[derive(Debug)]
+pub struct RangeBody<R> {
+    reader: R,
+    start: u64,
+    remaining: u64,
+    seeked: bool,
+    buf: Box<[u8]>,
+}
+
+impl<R> RangeBody<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    /// Creates a new [`RangeBody`] serving `range` of `reader`.
+    pub fn new(reader: R, range: Range<u64>) -> Self {
+        Self {
+            reader,
+            start: range.start,
+            remaining: range.end.saturating_sub(range.start),
+            seeked: false,
+            buf: vec![0; CHUNK_SIZE].into_boxed_slice(),
+        }
+    }
+}
+
+impl<R> Body for RangeBody<R>
+where
+    R: AsyncRead + AsyncSeek + Unpin,
+{
+    type Data = Bytes;
+    type Error = io::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        if this.remaining == 0 {
+            return Poll::Ready(None);
+        }
+
+        if !this.seeked {
+            let position =
+                ready!(Pin::new(&mut this.reader).poll_seek(cx, SeekFrom::Start(this.start)))?;
+            if position != this.start {
+                return Poll::Ready(Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "the source could not seek to the start of the range",
+                ))));
+            }
+            this.seeked = true;
+        }
+
+        // Offsets stay in `u64` and only the bounded chunk length is converted,
+        // so sources larger than `usize::MAX` can be served on 32-bit targets.
+        let len = usize::try_from(this.remaining)
+            .map_or(this.buf.len(), |remaining| remaining.min(this.buf.len()));
+        let read = ready!(Pin::new(&mut this.reader).poll_read(cx, &mut this.buf[..len]))?;
+        if read == 0 {
+            return Poll::Ready(Some(Err(io::ErrorKind::UnexpectedEof.into())));
+        }
+
+        this.remaining -= u64::try_from(read).expect("we do not support 128bit usize");
+        Poll::Ready(Some(Ok(Frame::data(Bytes::copy_from_slice(
+            &this.buf[..read],
+        )))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.remaining == 0
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.remaining)
+    }
+}
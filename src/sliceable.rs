@@ -0,0 +1,106 @@
+use std::{
+    fmt::{self, Debug},
+    ops::{Deref, Range},
+    sync::Arc,
+};
+
+use bytes::Bytes;
+
+/// A body that can be sliced without copying its content.
+///
+/// This is what [`serve_file_with_http_range`] requires from the body it serves.
+///
+/// [`serve_file_with_http_range`]: crate::serve_file_with_http_range
+pub trait Sliceable {
+    /// The type of a slice of the body.
+    type Slice;
+
+    /// Returns the length of the body, in bytes.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if the body is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the slice of the body covered by `range`.
+    ///
+    /// `range` is always within `0..self.len()`.
+    fn slice(self, range: Range<usize>) -> Self::Slice;
+}
+
+impl Sliceable for Bytes {
+    type Slice = Bytes;
+
+    fn len(&self) -> usize {
+        Bytes::len(self)
+    }
+
+    fn slice(self, range: Range<usize>) -> Self::Slice {
+        Bytes::slice(&self, range)
+    }
+}
+
+impl Sliceable for Arc<[u8]> {
+    type Slice = ArcSlice;
+
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    fn slice(self, range: Range<usize>) -> Self::Slice {
+        assert!(range.start <= range.end && range.end <= self.len());
+
+        ArcSlice { data: self, range }
+    }
+}
+
+/// A view into a range of an `Arc<[u8]>`, sharing ownership of the data.
+///
+/// It is the [`Sliceable::Slice`] of `Arc<[u8]>`, since the slice of an
+/// `Arc<[u8]>` cannot be another `Arc<[u8]>` without copying the bytes.
+#[derive(Clone)]
+pub struct ArcSlice {
+    data: Arc<[u8]>,
+    range: Range<usize>,
+}
+
+impl ArcSlice {
+    /// Returns the whole data this slice is a view into.
+    pub fn data(&self) -> &Arc<[u8]> {
+        &self.data
+    }
+
+    /// Returns the range of the data covered by this slice.
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl Deref for ArcSlice {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.data[self.range.clone()]
+    }
+}
+
+impl AsRef<[u8]> for ArcSlice {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl PartialEq for ArcSlice {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for ArcSlice {}
+
+impl Debug for ArcSlice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ArcSlice").field(&&**self).finish()
+    }
+}
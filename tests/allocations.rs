@@ -0,0 +1,67 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+use range_requests::headers::{
+    OrderedRange, content_range::HttpContentRange, range::HttpRange, ranges::HttpRanges,
+};
+
+/// Counts the allocations of the current thread, so that the tests running
+/// in parallel do not affect each other.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn parsing_valid_values_does_not_allocate() {
+    for value in ["bytes=0-99", " bytes=100- ", "BYTES=-50", "bytes=007-10"] {
+        let (range, allocations) = count_allocations(|| value.parse::<HttpRange>());
+        assert!(range.is_ok(), "{value:?}");
+        assert_eq!(allocations, 0, "{value:?}");
+    }
+    let (range, allocations) = count_allocations(|| HttpRange::parse_lenient("=0-99; a=b"));
+    assert!(range.is_ok());
+    assert_eq!(allocations, 0);
+    let (range, allocations) = count_allocations(|| HttpRange::parse_strict("bytes=0-99"));
+    assert!(range.is_ok());
+    assert_eq!(allocations, 0);
+
+    for value in ["bytes 0-99/200", "bytes 0-99/*", "bytes */200"] {
+        let (content_range, allocations) = count_allocations(|| value.parse::<HttpContentRange>());
+        assert!(content_range.is_ok(), "{value:?}");
+        assert_eq!(allocations, 0, "{value:?}");
+    }
+}
+
+#[test]
+fn parsing_multiple_ranges_only_allocates_the_list() {
+    let (ranges, allocations) = count_allocations(|| "bytes=0-0".parse::<HttpRanges>());
+    assert_eq!(
+        ranges.unwrap().iter().collect::<Vec<_>>(),
+        [&HttpRange::Range(OrderedRange::new(0..=0).unwrap())]
+    );
+    assert_eq!(allocations, 1);
+}